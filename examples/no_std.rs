@@ -0,0 +1,20 @@
+//! Uses the card types from a `#![no_std]` crate so that they stay usable on
+//! embedded targets. The library itself is checked with
+//! `cargo build --no-default-features`.
+
+#![no_std]
+
+use cards::card::max_conditional;
+use cards::card::standard::{deck, AceOrder, Card, Rank, Suit};
+
+pub fn full_deck() -> [Card; 52] {
+    deck()
+}
+
+pub fn aces() -> [Card; 4] {
+    Suit::all().map(|suit| Card::new(suit, Rank::Ace))
+}
+
+pub fn highest_rank(ranks: &[Rank]) -> Option<Rank> {
+    max_conditional(ranks, &AceOrder::High).copied()
+}
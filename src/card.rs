@@ -1,4 +1,4 @@
-use std::cmp::Ordering;
+use core::cmp::Ordering;
 
 pub trait ConditionalOrd {
     type Info;
@@ -6,7 +6,17 @@ pub trait ConditionalOrd {
     fn compare(&self, other: &Self, info: &Self::Info) -> Ordering;
 }
 
-#[derive(Debug, Clone, Copy)]
+#[cfg(feature = "std")]
+pub fn sort_conditional<T: ConditionalOrd>(items: &mut [T], info: &T::Info) {
+    items.sort_by(|a, b| a.compare(b, info));
+}
+
+pub fn max_conditional<'a, T: ConditionalOrd>(items: &'a [T], info: &T::Info) -> Option<&'a T> {
+    items.iter().max_by(|a, b| a.compare(b, info))
+}
+
+#[derive(Debug, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BaseCard<S, R>
 where
     S: ConditionalOrd + Sized,
@@ -24,12 +34,33 @@ where
     pub fn new(suit: S, rank: R) -> BaseCard<S, R> {
         BaseCard { suit, rank }
     }
+
+    // the same rank in another suit
+    pub fn with_suit(self, suit: S) -> BaseCard<S, R> {
+        BaseCard { suit, ..self }
+    }
+
+    // the same suit with another rank
+    pub fn with_rank(self, rank: R) -> BaseCard<S, R> {
+        BaseCard { rank, ..self }
+    }
 }
 
 pub mod standard {
-    use std::fmt::Display;
+    use core::cmp::Ordering;
+    use core::fmt::Display;
+    #[cfg(feature = "std")]
+    use core::str::FromStr;
+
+    #[cfg(feature = "std")]
+    use rand::seq::SliceRandom;
+    #[cfg(feature = "std")]
+    use rand::Rng;
+
+    use super::ConditionalOrd;
 
-    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub enum Suit {
         Hearts,
         Clubs,
@@ -37,8 +68,71 @@ pub mod standard {
         Spades,
     }
 
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+    pub enum Color {
+        Red,
+        Black,
+    }
+
+    impl Suit {
+        pub const fn all() -> [Suit; 4] {
+            [Suit::Hearts, Suit::Clubs, Suit::Diamonds, Suit::Spades]
+        }
+
+        // the suits in enum order
+        pub fn iter() -> impl Iterator<Item = Suit> {
+            Suit::all().into_iter()
+        }
+
+        pub fn color(&self) -> Color {
+            match self {
+                Suit::Hearts | Suit::Diamonds => Color::Red,
+                Suit::Clubs | Suit::Spades => Color::Black,
+            }
+        }
+
+        pub fn is_red(&self) -> bool {
+            self.color() == Color::Red
+        }
+
+        pub fn is_black(&self) -> bool {
+            self.color() == Color::Black
+        }
+
+        // the compact letter or glyph in either case, e.g. 'H', 's' or '♣'
+        pub fn from_char(c: char) -> Option<Suit> {
+            match c.to_ascii_uppercase() {
+                'H' | '♥' => Some(Suit::Hearts),
+                'C' | '♣' => Some(Suit::Clubs),
+                'D' | '♦' => Some(Suit::Diamonds),
+                'S' | '♠' => Some(Suit::Spades),
+                _ => None,
+            }
+        }
+
+        // the bridge bidding order, Clubs lowest to Spades highest
+        pub fn bridge_rank(&self) -> u8 {
+            match self {
+                Suit::Clubs => 0,
+                Suit::Diamonds => 1,
+                Suit::Hearts => 2,
+                Suit::Spades => 3,
+            }
+        }
+    }
+
     impl Display for Suit {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            // {:#} gives the compact unicode glyph
+            if f.alternate() {
+                return match self {
+                    Suit::Hearts => write!(f, "♥"),
+                    Suit::Clubs => write!(f, "♣"),
+                    Suit::Diamonds => write!(f, "♦"),
+                    Suit::Spades => write!(f, "♠"),
+                };
+            }
+
             match self {
                 Suit::Hearts => write!(f, "Hearts"),
                 Suit::Clubs => write!(f, "Clubs"),
@@ -48,7 +142,30 @@ pub mod standard {
         }
     }
 
-    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    // trumps beat the leading suit, which beats every other suit,
+    // and two other suits are Equal as neither can take the trick
+    pub fn suit_beats(a: Suit, b: Suit, leading: Suit, trumps: Option<Suit>) -> Ordering {
+        let tier = |suit: Suit| match suit {
+            suit if Some(suit) == trumps => 2,
+            suit if suit == leading => 1,
+            _ => 0,
+        };
+
+        tier(a).cmp(&tier(b))
+    }
+
+    impl ConditionalOrd for Suit {
+        // Leading card, optional trumps suit
+        type Info = (Suit, Option<Suit>);
+
+        fn compare(&self, other: &Self, info: &Self::Info) -> Ordering {
+            let (leading, trumps) = *info;
+            suit_beats(*self, *other, leading, trumps)
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub enum Rank {
         Ace,
         King,
@@ -63,10 +180,31 @@ pub mod standard {
         Four,
         Three,
         Two,
+        Joker,
     }
 
     impl Display for Rank {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            // {:#} gives the compact single character rank
+            if f.alternate() {
+                return match self {
+                    Rank::Ace => write!(f, "A"),
+                    Rank::Two => write!(f, "2"),
+                    Rank::Three => write!(f, "3"),
+                    Rank::Four => write!(f, "4"),
+                    Rank::Five => write!(f, "5"),
+                    Rank::Six => write!(f, "6"),
+                    Rank::Seven => write!(f, "7"),
+                    Rank::Eight => write!(f, "8"),
+                    Rank::Nine => write!(f, "9"),
+                    Rank::Ten => write!(f, "T"),
+                    Rank::Jack => write!(f, "J"),
+                    Rank::Queen => write!(f, "Q"),
+                    Rank::King => write!(f, "K"),
+                    Rank::Joker => write!(f, "★"),
+                };
+            }
+
             match self {
                 Rank::Ace => write!(f, "Ace"),
                 Rank::Two => write!(f, "Two"),
@@ -81,15 +219,185 @@ pub mod standard {
                 Rank::Jack => write!(f, "Jack"),
                 Rank::Queen => write!(f, "Queen"),
                 Rank::King => write!(f, "King"),
+                Rank::Joker => write!(f, "Joker"),
+            }
+        }
+    }
+
+    impl Rank {
+        // the thirteen standard ranks in Ace to King order, excludes the joker
+        pub const fn all() -> [Rank; 13] {
+            [
+                Rank::Ace,
+                Rank::Two,
+                Rank::Three,
+                Rank::Four,
+                Rank::Five,
+                Rank::Six,
+                Rank::Seven,
+                Rank::Eight,
+                Rank::Nine,
+                Rank::Ten,
+                Rank::Jack,
+                Rank::Queen,
+                Rank::King,
+            ]
+        }
+
+        // the standard ranks from Ace to King, without the joker
+        pub fn iter() -> impl Iterator<Item = Rank> {
+            Rank::all().into_iter()
+        }
+
+        pub fn value(&self) -> u8 {
+            match self {
+                Rank::Two => 2,
+                Rank::Three => 3,
+                Rank::Four => 4,
+                Rank::Five => 5,
+                Rank::Six => 6,
+                Rank::Seven => 7,
+                Rank::Eight => 8,
+                Rank::Nine => 9,
+                Rank::Ten => 10,
+                Rank::Jack => 11,
+                Rank::Queen => 12,
+                Rank::King => 13,
+                Rank::Ace => 14,
+                Rank::Joker => 15,
+            }
+        }
+
+        // the compact character in either case, 'T' for Ten, never the joker
+        pub fn from_char(c: char) -> Option<Rank> {
+            match c.to_ascii_uppercase() {
+                'A' => Some(Rank::Ace),
+                '2' => Some(Rank::Two),
+                '3' => Some(Rank::Three),
+                '4' => Some(Rank::Four),
+                '5' => Some(Rank::Five),
+                '6' => Some(Rank::Six),
+                '7' => Some(Rank::Seven),
+                '8' => Some(Rank::Eight),
+                '9' => Some(Rank::Nine),
+                'T' => Some(Rank::Ten),
+                'J' => Some(Rank::Jack),
+                'Q' => Some(Rank::Queen),
+                'K' => Some(Rank::King),
+                _ => None,
             }
         }
+
+        // position among the standard ranks from Two at 1 to Ace at 13,
+        // taken from the declaration order rather than value, None for the joker
+        pub fn rank_index(&self) -> Option<u8> {
+            match self {
+                Rank::Joker => None,
+                _ => Some(13 - *self as u8),
+            }
+        }
+
+        // standard ranks strictly above this one, None for the joker
+        pub fn ranks_above(&self) -> Option<u8> {
+            self.rank_index().map(|index| 13 - index)
+        }
+
+        // standard ranks strictly below this one, None for the joker
+        pub fn ranks_below(&self) -> Option<u8> {
+            self.rank_index().map(|index| index - 1)
+        }
+    }
+
+    impl PartialOrd for Rank {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Rank {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.value().cmp(&other.value())
+        }
+    }
+
+    // whether the ace ranks above the King or below the Two
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+    pub enum AceOrder {
+        #[default]
+        High,
+        Low,
+    }
+
+    impl ConditionalOrd for Rank {
+        type Info = AceOrder;
+
+        fn compare(&self, other: &Self, info: &Self::Info) -> Ordering {
+            let value = |rank: &Rank| match (rank, info) {
+                (Rank::Ace, AceOrder::Low) => 1,
+                _ => rank.value(),
+            };
+
+            value(self).cmp(&value(other))
+        }
     }
 
     pub type Card = super::BaseCard<Suit, Rank>;
 
+    impl ConditionalOrd for Card {
+        // Leading card, optional trumps suit
+        type Info = (Suit, Option<Suit>);
+
+        fn compare(&self, other: &Self, info: &Self::Info) -> Ordering {
+            match self.suit.compare(&other.suit, info) {
+                Ordering::Equal => self.rank.compare(&other.rank, &AceOrder::High),
+                other => other,
+            }
+        }
+    }
+
     impl From<Card> for u32 {
         fn from(card: Card) -> Self {
-            (card.suit as u32) * 13 + (card.rank as u32)
+            // jokers are distinguished by suit and encoded after the 52 standard cards
+            match card.rank {
+                Rank::Joker => 52 + card.suit as u32,
+                _ => (card.suit as u32) * 13 + (card.rank as u32),
+            }
+        }
+    }
+
+    impl TryFrom<u32> for Card {
+        type Error = u32;
+
+        fn try_from(value: u32) -> Result<Self, Self::Error> {
+            // inverse of From<Card> for u32, so must follow discriminant order
+            const RANKS: [Rank; 13] = [
+                Rank::Ace,
+                Rank::King,
+                Rank::Queen,
+                Rank::Jack,
+                Rank::Ten,
+                Rank::Nine,
+                Rank::Eight,
+                Rank::Seven,
+                Rank::Six,
+                Rank::Five,
+                Rank::Four,
+                Rank::Three,
+                Rank::Two,
+            ];
+
+            if value >= 56 {
+                return Err(value);
+            }
+
+            if value >= 52 {
+                return Ok(Card::new(Suit::all()[(value - 52) as usize], Rank::Joker));
+            }
+
+            Ok(Card::new(
+                Suit::all()[(value / 13) as usize],
+                RANKS[(value % 13) as usize],
+            ))
         }
     }
 
@@ -99,36 +407,1039 @@ pub mod standard {
         }
     }
 
+    // the derived Hash on BaseCard hashes both suit and rank, matching eq
+    impl Eq for Card {}
+
+    // the canonical order for display, suit-major and agreeing with the u32 encoding
+    impl PartialOrd for Card {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Card {
+        fn cmp(&self, other: &Self) -> Ordering {
+            u32::from(*self).cmp(&u32::from(*other))
+        }
+    }
+
     impl Display for Card {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "{} of {}", self.rank, self.suit)
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            if f.alternate() {
+                return match self.rank {
+                    Rank::Joker => write!(f, "{:#}", self.rank),
+                    _ => write!(f, "{:#}{:#}", self.rank, self.suit),
+                };
+            }
+
+            match self.rank {
+                Rank::Joker => write!(f, "{}", self.rank),
+                _ => write!(f, "{} of {}", self.rank, self.suit),
+            }
         }
     }
 
+    impl Card {
+        // ranks between the two cards either way, by rank index with aces high,
+        // None if either is a joker
+        pub fn rank_distance(&self, other: &Card) -> Option<u8> {
+            Some(self.rank.rank_index()?.abs_diff(other.rank.rank_index()?))
+        }
+
+        // whether the ranks are next to each other, e.g. for straights,
+        // a joker is next to nothing
+        pub fn is_adjacent(&self, other: &Card) -> bool {
+            self.rank_distance(other) == Some(1)
+        }
+
+        pub fn is_of_suit(&self, suit: Suit) -> bool {
+            self.suit == suit
+        }
+
+        // never true when playing without trumps
+        pub fn is_trump(&self, trumps: Option<Suit>) -> bool {
+            trumps.is_some_and(|trumps| self.is_of_suit(trumps))
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl Card {
+        // a boxed card for terminal games, the rank in the corners and the suit glyph centred
+        pub fn ascii_art(&self) -> String {
+            let rank = format!("{:#}", self.rank);
+            let suit = format!("{:#}", self.suit);
+
+            [
+                "+-----+".to_string(),
+                format!("|{:<5}|", rank),
+                format!("|  {}  |", suit),
+                format!("|{:>5}|", rank),
+                "+-----+".to_string(),
+            ]
+            .join("\n")
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct ParseCardError(String);
+
+    #[cfg(feature = "std")]
+    impl Display for ParseCardError {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "cannot parse {:?} as a card", self.0)
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for ParseCardError {}
+
+    #[cfg(feature = "std")]
+    impl FromStr for Card {
+        type Err = ParseCardError;
+
+        // accepts the compact "AH"/"10S"/"T♠" form or the verbose "Ten of Spades" form
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let err = || ParseCardError(s.to_string());
+            let s = s.trim();
+
+            if let Some((rank, suit)) = s.split_once(" of ") {
+                let rank = Rank::all()
+                    .into_iter()
+                    .find(|x| x.to_string().eq_ignore_ascii_case(rank.trim()))
+                    .ok_or_else(err)?;
+
+                let suit = Suit::all()
+                    .into_iter()
+                    .find(|x| x.to_string().eq_ignore_ascii_case(suit.trim()))
+                    .ok_or_else(err)?;
+
+                return Ok(Card::new(suit, rank));
+            }
+
+            let mut chars = s.chars();
+            let suit = chars
+                .next_back()
+                .and_then(Suit::from_char)
+                .ok_or_else(err)?;
+
+            let rank = match chars.as_str() {
+                "10" => Rank::Ten,
+                rank => {
+                    let mut chars = rank.chars();
+
+                    match (chars.next(), chars.next()) {
+                        (Some(rank), None) => Rank::from_char(rank).ok_or_else(err)?,
+                        _ => return Err(err()),
+                    }
+                }
+            };
+
+            Ok(Card::new(suit, rank))
+        }
+    }
+
+    // every standard card, in suit order and then Ace to King, the same order as
+    // deck() but not the u32 encoding, which Card::try_from inverts instead
+    pub const CARDS: [Card; 52] = {
+        let mut cards = [Card {
+            suit: Suit::Hearts,
+            rank: Rank::Ace,
+        }; 52];
+
+        let mut i = 0;
+
+        while i < 52 {
+            cards[i] = Card {
+                suit: Suit::all()[i / 13],
+                rank: Rank::all()[i % 13],
+            };
+            i += 1;
+        }
+
+        cards
+    };
+
     pub fn deck() -> [Card; 52] {
-        [Suit::Hearts, Suit::Clubs, Suit::Diamonds, Suit::Spades]
+        CARDS
+    }
+
+    // cards of each suit in a hand, in suit order, e.g. for finding voids
+    pub fn suit_counts(hand: &[Option<Card>]) -> [u8; 4] {
+        let mut counts = [0; 4];
+
+        for card in hand.iter().flatten() {
+            counts[card.suit as usize] += 1;
+        }
+
+        counts
+    }
+
+    // cards of each standard rank in a hand from Two to Ace, jokers are skipped
+    pub fn rank_counts(hand: &[Option<Card>]) -> [u8; 13] {
+        let mut counts = [0; 13];
+
+        for index in hand
             .iter()
-            .flat_map(|suit| {
-                [
-                    Rank::Ace,
-                    Rank::Two,
-                    Rank::Three,
-                    Rank::Four,
-                    Rank::Five,
-                    Rank::Six,
-                    Rank::Seven,
-                    Rank::Eight,
-                    Rank::Nine,
-                    Rank::Ten,
-                    Rank::Jack,
-                    Rank::Queen,
-                    Rank::King,
-                ]
-                .iter()
-                .map(move |rank| Card::new(*suit, *rank))
+            .flatten()
+            .filter_map(|card| card.rank.rank_index())
+        {
+            counts[index as usize - 1] += 1;
+        }
+
+        counts
+    }
+
+    // the Milton Work point count of a hand, A=4, K=3, Q=2, J=1
+    pub fn high_card_points(hand: &[Option<Card>]) -> u8 {
+        hand.iter()
+            .flatten()
+            .map(|card| match card.rank {
+                Rank::Ace => 4,
+                Rank::King => 3,
+                Rank::Queen => 2,
+                Rank::Jack => 1,
+                _ => 0,
             })
-            .collect::<Vec<_>>()
-            .try_into()
-            .unwrap()
+            .sum()
+    }
+
+    #[cfg(feature = "std")]
+    pub fn deck_with_jokers(count: usize) -> Vec<Card> {
+        assert!(count <= 4, "at most one joker per suit");
+
+        let jokers = Suit::all()
+            .into_iter()
+            .take(count)
+            .map(|suit| Card::new(suit, Rank::Joker));
+
+        deck().into_iter().chain(jokers).collect()
+    }
+
+    #[cfg(feature = "std")]
+    // a stripped deck with only the given ranks of every suit, e.g. for Euchre or Piquet
+    pub fn deck_from_ranks(ranks: &[Rank]) -> Vec<Card> {
+        Suit::all()
+            .into_iter()
+            .flat_map(|suit| ranks.iter().map(move |rank| Card::new(suit, *rank)))
+            .collect()
+    }
+
+    #[cfg(feature = "std")]
+    // a copy of an explicit list of cards for rigged scenarios, panics on a duplicate
+    pub fn deck_from(cards: &[Card]) -> Vec<Card> {
+        if let Err(error) = validate_deck(cards) {
+            panic!("{}", error);
+        }
+
+        cards.to_vec()
+    }
+
+    #[cfg(feature = "std")]
+    // checks a custom deck holds no card twice, reporting the first repeat
+    pub fn validate_deck(cards: &[Card]) -> Result<(), crate::error::CardsError> {
+        match cards
+            .iter()
+            .enumerate()
+            .find(|(i, card)| cards[..*i].contains(card))
+        {
+            Some((_, card)) => Err(crate::error::CardsError::DuplicateCard(*card)),
+            None => Ok(()),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    // Marsaglia's xorshift64, pinned here so stable shuffles never depend on rand
+    struct XorShift64(u64);
+
+    #[cfg(feature = "std")]
+    impl XorShift64 {
+        fn new(seed: u64) -> XorShift64 {
+            // the all zero state is a fixed point
+            match seed ^ 0x9E37_79B9_7F4A_7C15 {
+                0 => XorShift64(1),
+                state => XorShift64(state),
+            }
+        }
+
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    #[cfg(feature = "std")]
+    // a pile of cards drawn from the top, which is the end of the vec
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Deck {
+        cards: Vec<Card>,
+    }
+
+    #[cfg(feature = "std")]
+    impl Deck {
+        pub fn new() -> Deck {
+            Deck {
+                cards: deck().to_vec(),
+            }
+        }
+
+        // a stacked deck where the first card is drawn first
+        pub fn from_cards(cards: &[Card]) -> Deck {
+            let mut cards = deck_from(cards);
+            cards.reverse();
+
+            Deck { cards }
+        }
+
+        pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+            self.cards.shuffle(rng);
+        }
+
+        // Fisher-Yates driven by an in-crate xorshift64, so a seed gives the
+        // same order on every platform and every version of rand
+        pub fn shuffle_stable(&mut self, seed: u64) {
+            let mut rng = XorShift64::new(seed);
+
+            for i in (1..self.cards.len()).rev() {
+                let j = (rng.next() % (i as u64 + 1)) as usize;
+                self.cards.swap(i, j);
+            }
+        }
+
+        pub fn draw(&mut self) -> Option<Card> {
+            self.cards.pop()
+        }
+
+        // as draw, with an error for an empty deck
+        pub fn try_draw(&mut self) -> Result<Card, crate::error::CardsError> {
+            self.draw().ok_or(crate::error::CardsError::DeckEmpty)
+        }
+
+        // moves the cards to the top so they are drawn first and in order,
+        // adding any that are not already in the deck
+        pub fn stack_top(&mut self, cards: &[Card]) {
+            self.cards.retain(|card| !cards.contains(card));
+            self.cards.extend(cards.iter().rev());
+        }
+
+        // returns a card to the top, to be drawn next
+        pub fn put(&mut self, card: Card) {
+            self.cards.push(card);
+        }
+
+        // deals every remaining card round-robin from the top into `hands` hands
+        pub fn deal(&mut self, hands: usize) -> Vec<Vec<Card>> {
+            let mut dealt = vec![Vec::new(); hands];
+
+            for (i, card) in self.deal_iter(hands) {
+                dealt[i].push(card);
+            }
+
+            dealt
+        }
+
+        // draws every remaining card lazily, paired with the player it is dealt
+        // to, round-robin from player 0
+        pub fn deal_iter(&mut self, players: usize) -> impl Iterator<Item = (usize, Card)> + '_ {
+            assert!(players > 0, "cards are dealt to at least one player");
+
+            (0..).map_while(move |i| self.draw().map(|card| (i % players, card)))
+        }
+
+        pub fn remaining(&self) -> usize {
+            self.cards.len()
+        }
+
+        // the cards left, from the bottom up to the top
+        pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Card> {
+            self.cards.iter()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.cards.is_empty()
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl Default for Deck {
+        fn default() -> Self {
+            Deck::new()
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl From<Vec<Card>> for Deck {
+        fn from(cards: Vec<Card>) -> Self {
+            Deck { cards }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    // face up cards in the order they were put down, e.g. a discard pile
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct Pile {
+        cards: Vec<Card>,
+    }
+
+    #[cfg(feature = "std")]
+    impl Pile {
+        pub fn new() -> Pile {
+            Pile::default()
+        }
+
+        pub fn push(&mut self, card: Card) {
+            self.cards.push(card);
+        }
+
+        // takes back the card put down last
+        pub fn pop(&mut self) -> Option<Card> {
+            self.cards.pop()
+        }
+
+        // the card put down last
+        pub fn top(&self) -> Option<&Card> {
+            self.cards.last()
+        }
+
+        pub fn contains(&self, card: &Card) -> bool {
+            self.cards.contains(card)
+        }
+
+        pub fn len(&self) -> usize {
+            self.cards.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.cards.is_empty()
+        }
+
+        // from the first card put down to the last
+        pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Card> {
+            self.cards.iter()
+        }
+
+        pub fn as_slice(&self) -> &[Card] {
+            &self.cards
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+    use std::collections::HashSet;
+
+    use rand::prelude::*;
+
+    use super::standard::{
+        deck, deck_from, deck_from_ranks, deck_with_jokers, high_card_points, rank_counts,
+        suit_beats, suit_counts, validate_deck, AceOrder, Card, Color, Deck, Pile, Rank, Suit,
+        CARDS,
+    };
+    use super::{max_conditional, sort_conditional, ConditionalOrd};
+
+    #[test]
+    fn test_rank_ord() {
+        let sorted = vec![
+            Rank::Two,
+            Rank::Three,
+            Rank::Four,
+            Rank::Five,
+            Rank::Six,
+            Rank::Seven,
+            Rank::Eight,
+            Rank::Nine,
+            Rank::Ten,
+            Rank::Jack,
+            Rank::Queen,
+            Rank::King,
+            Rank::Ace,
+        ];
+
+        let mut ranks = sorted.clone();
+        ranks.shuffle(&mut SmallRng::seed_from_u64(0));
+        ranks.sort();
+
+        assert_eq!(ranks, sorted);
+        assert!(Rank::Ace > Rank::King);
+    }
+
+    #[test]
+    fn test_rank_value() {
+        assert_eq!(Rank::Ace.value(), 14);
+        assert_eq!(Rank::Two.value(), 2);
+    }
+
+    #[test]
+    fn test_card_from_u32() {
+        for i in 0..52 {
+            let card = Card::try_from(i).unwrap();
+            assert_eq!(u32::from(card), i);
+        }
+
+        for card in deck() {
+            assert_eq!(Card::try_from(u32::from(card)), Ok(card));
+        }
+
+        for i in 52..56 {
+            let card = Card::try_from(i).unwrap();
+            assert_eq!(card.rank, Rank::Joker);
+            assert_eq!(u32::from(card), i);
+        }
+
+        assert_eq!(Card::try_from(56), Err(56));
+    }
+
+    #[test]
+    fn test_deck_with_jokers() {
+        let deck = deck_with_jokers(2);
+        assert_eq!(deck.len(), 54);
+
+        let jokers: Vec<_> = deck.iter().filter(|c| c.rank == Rank::Joker).collect();
+        assert_eq!(jokers.len(), 2);
+        assert_ne!(jokers[0], jokers[1]);
+        assert_ne!(u32::from(*jokers[0]), u32::from(*jokers[1]));
+        assert_eq!(jokers[0].to_string(), "Joker");
+    }
+
+    #[test]
+    fn test_max_conditional() {
+        let mut rng = SmallRng::seed_from_u64(1);
+
+        for _ in 0..10 {
+            let mut ranks: Vec<_> = deck().iter().take(20).map(|c| c.rank).collect();
+            ranks.shuffle(&mut rng);
+
+            let max = *max_conditional(&ranks, &AceOrder::High).unwrap();
+            sort_conditional(&mut ranks, &AceOrder::High);
+
+            assert_eq!(max, *ranks.last().unwrap());
+        }
+
+        assert_eq!(max_conditional::<Rank>(&[], &AceOrder::High), None);
+    }
+
+    #[test]
+    fn test_all() {
+        assert_eq!(Suit::all().len(), 4);
+        assert_eq!(Rank::all().len(), 13);
+        assert_eq!(Rank::all().first(), Some(&Rank::Ace));
+        assert_eq!(Rank::all().last(), Some(&Rank::King));
+        assert!(!Rank::all().contains(&Rank::Joker));
+
+        assert_eq!(deck()[0], Card::new(Suit::Hearts, Rank::Ace));
+        assert_eq!(deck()[51], Card::new(Suit::Spades, Rank::King));
+    }
+
+    #[test]
+    fn test_display() {
+        let card = Card::new(Suit::Hearts, Rank::Ace);
+        assert_eq!(format!("{}", card), "Ace of Hearts");
+        assert_eq!(format!("{:#}", card), "A♥");
+
+        let card = Card::new(Suit::Spades, Rank::Ten);
+        assert_eq!(format!("{:#}", card), "T♠");
+    }
+
+    #[test]
+    fn test_iter() {
+        let suits: Vec<_> = Suit::iter().collect();
+        assert_eq!(suits.len(), 4);
+        assert_eq!(suits.first(), Some(&Suit::Hearts));
+        assert_eq!(suits.last(), Some(&Suit::Spades));
+
+        let ranks: Vec<_> = Rank::iter().collect();
+        assert_eq!(ranks.len(), 13);
+        assert_eq!(ranks.first(), Some(&Rank::Ace));
+        assert_eq!(ranks.last(), Some(&Rank::King));
+    }
+
+    #[test]
+    fn test_rank_distance() {
+        let two = Card::new(Suit::Hearts, Rank::Two);
+        let five = Card::new(Suit::Spades, Rank::Five);
+
+        assert_eq!(two.rank_distance(&five), Some(3));
+        assert_eq!(five.rank_distance(&two), Some(3));
+        assert_eq!(
+            five.rank_distance(&Card::new(Suit::Clubs, Rank::Five)),
+            Some(0)
+        );
+
+        assert!(Card::new(Suit::Clubs, Rank::King).is_adjacent(&Card::new(Suit::Clubs, Rank::Ace)));
+        assert!(!two.is_adjacent(&five));
+
+        // the joker has no distance from anything, itself included
+        let joker = Card::new(Suit::Hearts, Rank::Joker);
+        assert_eq!(joker.rank_distance(&two), None);
+        assert_eq!(two.rank_distance(&joker), None);
+        assert_eq!(joker.rank_distance(&joker), None);
+        assert!(!joker.is_adjacent(&Card::new(Suit::Hearts, Rank::Ace)));
+    }
+
+    #[test]
+    fn test_is_trump() {
+        let card = Card::new(Suit::Diamonds, Rank::Six);
+
+        assert!(card.is_trump(Some(Suit::Diamonds)));
+        assert!(!card.is_trump(Some(Suit::Clubs)));
+        assert!(!card.is_trump(None));
+
+        assert!(card.is_of_suit(Suit::Diamonds));
+        assert!(!card.is_of_suit(Suit::Hearts));
+    }
+
+    #[test]
+    fn test_ascii_art() {
+        let art = Card::new(Suit::Spades, Rank::Queen).ascii_art();
+        let lines: Vec<_> = art.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[1], "|Q    |");
+        assert_eq!(lines[2], "|  ♠  |");
+        assert_eq!(lines[3], "|    Q|");
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("AH".parse(), Ok(Card::new(Suit::Hearts, Rank::Ace)));
+        assert_eq!("ts".parse(), Ok(Card::new(Suit::Spades, Rank::Ten)));
+        assert_eq!("10S".parse(), Ok(Card::new(Suit::Spades, Rank::Ten)));
+        assert_eq!("Q♦".parse(), Ok(Card::new(Suit::Diamonds, Rank::Queen)));
+        assert_eq!(
+            "Ten of Spades".parse(),
+            Ok(Card::new(Suit::Spades, Rank::Ten))
+        );
+
+        for card in deck() {
+            assert_eq!(card.to_string().parse(), Ok(card));
+            assert_eq!(format!("{:#}", card).parse(), Ok(card));
+        }
+
+        let err = "ZZ".parse::<Card>().unwrap_err();
+        assert_eq!(err.to_string(), "cannot parse \"ZZ\" as a card");
+        assert!("".parse::<Card>().is_err());
+        assert!("Eleven of Hearts".parse::<Card>().is_err());
+    }
+
+    #[test]
+    fn test_deck_draw() {
+        let mut deck = Deck::new();
+        deck.shuffle(&mut SmallRng::seed_from_u64(0));
+
+        let mut drawn = Vec::new();
+
+        while let Some(card) = deck.draw() {
+            assert!(!drawn.contains(&card));
+            drawn.push(card);
+        }
+
+        assert_eq!(drawn.len(), 52);
+        assert_eq!(deck.remaining(), 0);
+        assert_eq!(deck.draw(), None);
+    }
+
+    #[test]
+    fn test_deck_deal() {
+        let mut deck = Deck::new();
+        let hands = deck.deal(4);
+
+        assert_eq!(hands.len(), 4);
+        assert!(hands.iter().all(|hand| hand.len() == 13));
+        assert!(deck.is_empty());
+
+        // the top card goes to the first hand
+        assert_eq!(hands[0][0], Card::new(Suit::Spades, Rank::King));
+    }
+
+    #[test]
+    fn test_deck_deal_iter() {
+        let mut deck = Deck::new();
+        let dealt: Vec<_> = deck.deal_iter(4).collect();
+
+        assert_eq!(dealt.len(), 52);
+        assert!(deck.is_empty());
+
+        for (i, (player, _)) in dealt.iter().enumerate() {
+            assert_eq!(*player, i % 4);
+        }
+
+        for player in 0..4 {
+            assert_eq!(dealt.iter().filter(|(p, _)| *p == player).count(), 13);
+        }
+
+        // the same order as dealing every hand at once
+        let hands = Deck::new().deal(4);
+        assert_eq!(dealt[4], (0, hands[0][1]));
+    }
+
+    #[test]
+    fn test_deck_from_ranks() {
+        let ranks: Vec<_> = Rank::all()
+            .into_iter()
+            .filter(|rank| *rank >= Rank::Nine)
+            .collect();
+
+        let euchre = deck_from_ranks(&ranks);
+
+        assert_eq!(euchre.len(), 24);
+        assert!(euchre.iter().all(|card| card.rank != Rank::Two));
+        assert!(euchre.contains(&Card::new(Suit::Spades, Rank::Nine)));
+    }
+
+    #[test]
+    fn test_hash() {
+        let cards: HashSet<Card> = deck().into_iter().collect();
+        assert_eq!(cards.len(), 52);
+        assert!(cards.contains(&Card::new(Suit::Clubs, Rank::Seven)));
+
+        let jokers: HashSet<Card> = deck_with_jokers(2).into_iter().collect();
+        assert_eq!(jokers.len(), 54);
+    }
+
+    #[test]
+    fn test_deck_order() {
+        let mut expected = Vec::new();
+
+        for suit in Suit::all() {
+            for rank in Rank::all() {
+                expected.push(Card::new(suit, rank));
+            }
+        }
+
+        assert_eq!(deck().to_vec(), expected);
+        assert_eq!(deck_from_ranks(&Rank::all()), expected);
+    }
+
+    #[test]
+    fn test_suit_color() {
+        assert_eq!(Suit::Hearts.color(), Color::Red);
+        assert_eq!(Suit::Diamonds.color(), Color::Red);
+        assert_eq!(Suit::Clubs.color(), Color::Black);
+        assert_eq!(Suit::Spades.color(), Color::Black);
+
+        assert!(Suit::Hearts.is_red() && !Suit::Hearts.is_black());
+        assert!(Suit::Spades.is_black() && !Suit::Spades.is_red());
+    }
+
+    #[test]
+    fn test_card_ord() {
+        let mut hand = deck_with_jokers(2);
+        hand.shuffle(&mut SmallRng::seed_from_u64(3));
+        hand.sort();
+
+        let expected: Vec<_> = (0..54).map(|i| Card::try_from(i).unwrap()).collect();
+        assert_eq!(hand, expected);
+
+        assert!(Card::new(Suit::Hearts, Rank::Two) < Card::new(Suit::Clubs, Rank::Ace));
+        assert!(Card::new(Suit::Spades, Rank::Ace) < Card::new(Suit::Spades, Rank::King));
+    }
+
+    #[test]
+    fn test_deck_from_cards() {
+        let cards: Vec<Card> = ["AS", "KH", "2C", "TD", "9S"]
+            .iter()
+            .map(|card| card.parse().unwrap())
+            .collect();
+
+        assert_eq!(deck_from(&cards), cards);
+
+        let mut deck = Deck::from_cards(&cards);
+        let hands = deck.deal(4);
+
+        assert_eq!(hands[0], vec![cards[0], cards[4]]);
+        assert_eq!(hands[1], vec![cards[1]]);
+        assert_eq!(hands[2], vec![cards[2]]);
+        assert_eq!(hands[3], vec![cards[3]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "appears twice")]
+    fn test_deck_from_duplicate() {
+        let card = Card::new(Suit::Hearts, Rank::Ace);
+        deck_from(&[card, Card::new(Suit::Clubs, Rank::Ace), card]);
+    }
+
+    #[test]
+    fn test_rank_index() {
+        assert_eq!(Rank::Two.rank_index(), Some(1));
+        assert_eq!(Rank::Ten.rank_index(), Some(9));
+        assert_eq!(Rank::Ace.rank_index(), Some(13));
+
+        assert_eq!(Rank::King.ranks_above(), Some(1));
+        assert_eq!(Rank::Two.ranks_below(), Some(0));
+        assert_eq!(Rank::Seven.ranks_above(), Some(7));
+        assert_eq!(Rank::Seven.ranks_below(), Some(5));
+
+        for rank in Rank::all() {
+            let above = Rank::all().iter().filter(|other| **other > rank).count();
+            assert_eq!(rank.ranks_above(), Some(above as u8));
+        }
+
+        // the joker sits outside the standard ranks
+        assert_eq!(Rank::Joker.rank_index(), None);
+        assert_eq!(Rank::Joker.ranks_above(), None);
+        assert_eq!(Rank::Joker.ranks_below(), None);
+    }
+
+    #[test]
+    fn test_ace_order() {
+        let (ace, king, two) = (Rank::Ace, Rank::King, Rank::Two);
+
+        assert_eq!(ace.compare(&king, &AceOrder::High), Ordering::Greater);
+        assert_eq!(ace.compare(&king, &AceOrder::Low), Ordering::Less);
+        assert_eq!(ace.compare(&two, &AceOrder::Low), Ordering::Less);
+        assert_eq!(king.compare(&two, &AceOrder::Low), Ordering::Greater);
+        assert_eq!(ace.compare(&ace, &AceOrder::Low), Ordering::Equal);
+
+        let mut ranks = Rank::all();
+        ranks.reverse();
+        sort_conditional(&mut ranks, &AceOrder::Low);
+        assert_eq!(ranks, Rank::all());
+    }
+
+    #[test]
+    fn test_shuffle_stable() {
+        let mut a = Deck::new();
+        let mut b = Deck::new();
+        a.shuffle_stable(2024);
+        b.shuffle_stable(2024);
+
+        assert_eq!(a, b);
+        assert_ne!(a, Deck::new());
+
+        // pinned, a change here breaks every recorded stable shuffle
+        assert_eq!(a.draw(), Some("6C".parse().unwrap()));
+        assert_eq!(a.draw(), Some("JC".parse().unwrap()));
+        assert_eq!(a.draw(), Some("TH".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_counts() {
+        let mut hand: Vec<Option<Card>> = [
+            "AS", "KS", "QS", "2S", "AH", "TH", "9H", "3H", "KD", "7D", "2D", "9S", "5S",
+        ]
+        .iter()
+        .map(|card| Some(card.parse().unwrap()))
+        .collect();
+
+        hand.push(None);
+
+        // void in clubs
+        assert_eq!(suit_counts(&hand), [4, 0, 3, 6]);
+
+        let ranks = rank_counts(&hand);
+        assert_eq!(ranks.iter().sum::<u8>(), 13);
+        let index = |rank: Rank| rank.rank_index().unwrap() as usize - 1;
+        assert_eq!(ranks[index(Rank::Two)], 2);
+        assert_eq!(ranks[index(Rank::Ace)], 2);
+        assert_eq!(ranks[index(Rank::Four)], 0);
+    }
+
+    #[test]
+    fn test_high_card_points() {
+        let hand = |cards: &[&str]| -> Vec<Option<Card>> {
+            cards
+                .iter()
+                .map(|card| Some(card.parse().unwrap()))
+                .collect()
+        };
+
+        assert_eq!(high_card_points(&hand(&["AS", "KH", "QD", "JC", "2S"])), 10);
+        assert_eq!(high_card_points(&hand(&["TS", "9H", "2D", "5C"])), 0);
+        assert_eq!(high_card_points(&[None, None]), 0);
+    }
+
+    #[test]
+    fn test_suit_beats() {
+        let trumps = Some(Suit::Spades);
+
+        // trumps beat a plain suit, led or not
+        assert_eq!(
+            suit_beats(Suit::Spades, Suit::Clubs, Suit::Hearts, trumps),
+            Ordering::Greater
+        );
+        assert_eq!(
+            suit_beats(Suit::Hearts, Suit::Spades, Suit::Hearts, trumps),
+            Ordering::Less
+        );
+
+        // the leading suit beats an off suit, two off suits tie
+        assert_eq!(
+            suit_beats(Suit::Hearts, Suit::Clubs, Suit::Hearts, None),
+            Ordering::Greater
+        );
+        assert_eq!(
+            suit_beats(Suit::Diamonds, Suit::Clubs, Suit::Hearts, None),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_suit_compare_antisymmetric() {
+        let trumps = [Some(Suit::Hearts), Some(Suit::Spades), None];
+
+        for info in Suit::all()
+            .into_iter()
+            .flat_map(|leading| trumps.map(|t| (leading, t)))
+        {
+            for a in Suit::all() {
+                for b in Suit::all() {
+                    assert_eq!(a.compare(&b, &info), b.compare(&a, &info).reverse());
+                }
+            }
+        }
+
+        let info = (Suit::Hearts, Some(Suit::Spades));
+        assert_eq!(
+            Suit::Spades.compare(&Suit::Hearts, &info),
+            Ordering::Greater
+        );
+        assert_eq!(Suit::Hearts.compare(&Suit::Clubs, &info), Ordering::Greater);
+        assert_eq!(Suit::Clubs.compare(&Suit::Diamonds, &info), Ordering::Equal);
+
+        // a low card of the leading suit beats a high off-suit card
+        let trick = [
+            Card::new(Suit::Hearts, Rank::Two),
+            Card::new(Suit::Clubs, Rank::Ace),
+            Card::new(Suit::Diamonds, Rank::King),
+        ];
+        assert_eq!(max_conditional(&trick, &info), Some(&trick[0]));
+    }
+
+    #[test]
+    fn test_stack_top() {
+        let mut deck = Deck::new();
+        deck.shuffle(&mut SmallRng::seed_from_u64(5));
+
+        let cards = [
+            Card::new(Suit::Diamonds, Rank::Queen),
+            Card::new(Suit::Hearts, Rank::Four),
+        ];
+        deck.stack_top(&cards);
+
+        assert_eq!(deck.remaining(), 52);
+        assert_eq!(deck.draw(), Some(cards[0]));
+        assert_eq!(deck.draw(), Some(cards[1]));
+        assert!(deck.iter().all(|card| !cards.contains(card)));
+    }
+
+    #[test]
+    fn test_bridge_rank() {
+        assert!(Suit::Spades.bridge_rank() > Suit::Hearts.bridge_rank());
+        assert!(Suit::Hearts.bridge_rank() > Suit::Diamonds.bridge_rank());
+        assert!(Suit::Diamonds.bridge_rank() > Suit::Clubs.bridge_rank());
+
+        let mut suits = Suit::all();
+        suits.sort_by_key(Suit::bridge_rank);
+        assert_eq!(
+            suits,
+            [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades]
+        );
+
+        // the card encoding still follows declaration order
+        assert_eq!(u32::from(Card::new(Suit::Clubs, Rank::Ace)), 13);
+    }
+
+    #[test]
+    fn test_with_suit_and_rank() {
+        let card = Card::new(Suit::Hearts, Rank::Queen);
+
+        let spade = card.with_suit(Suit::Spades);
+        let king = card.with_rank(Rank::King);
+
+        assert_eq!(spade, Card::new(Suit::Spades, Rank::Queen));
+        assert_eq!(king, Card::new(Suit::Hearts, Rank::King));
+        assert_eq!(card, Card::new(Suit::Hearts, Rank::Queen));
+
+        assert_eq!(
+            card.with_suit(Suit::Clubs).with_rank(Rank::Two),
+            Card::new(Suit::Clubs, Rank::Two)
+        );
+    }
+
+    #[test]
+    fn test_from_char() {
+        for (c, suit) in "HCDS".chars().zip(Suit::all()) {
+            assert_eq!(Suit::from_char(c), Some(suit));
+            assert_eq!(Suit::from_char(c.to_ascii_lowercase()), Some(suit));
+        }
+
+        for (c, rank) in "A23456789TJQK".chars().zip(Rank::all()) {
+            assert_eq!(Rank::from_char(c), Some(rank));
+            assert_eq!(Rank::from_char(c.to_ascii_lowercase()), Some(rank));
+            assert_eq!(format!("{:#}", rank).chars().next(), Some(c));
+        }
+
+        assert_eq!(Suit::from_char('X'), None);
+        assert_eq!(Suit::from_char('1'), None);
+        assert_eq!(Rank::from_char('1'), None);
+        assert_eq!(Rank::from_char('Z'), None);
+    }
+
+    #[test]
+    fn test_cards_const() {
+        assert_eq!(CARDS.len(), 52);
+        assert_eq!(CARDS, deck());
+
+        const FIRST: Card = CARDS[0];
+        assert_eq!(FIRST, Card::new(Suit::Hearts, Rank::Ace));
+    }
+
+    #[test]
+    fn test_validate_deck() {
+        assert_eq!(validate_deck(&deck()), Ok(()));
+        assert_eq!(
+            validate_deck(&deck_from_ranks(&[Rank::Ace, Rank::King])),
+            Ok(())
+        );
+
+        let ace = Card::new(Suit::Spades, Rank::Ace);
+        let mut cards = deck().to_vec();
+        cards.push(ace);
+
+        assert_eq!(
+            validate_deck(&cards),
+            Err(crate::error::CardsError::DuplicateCard(ace))
+        );
+    }
+
+    #[test]
+    fn test_pile() {
+        let mut pile = Pile::new();
+        assert!(pile.is_empty());
+        assert_eq!(pile.top(), None);
+
+        let first = Card::new(Suit::Clubs, Rank::Eight);
+        let second = Card::new(Suit::Hearts, Rank::Three);
+
+        pile.push(first);
+        pile.push(second);
+
+        assert_eq!(pile.len(), 2);
+        assert_eq!(pile.top(), Some(&second));
+        assert!(pile.contains(&first));
+        assert!(!pile.contains(&Card::new(Suit::Spades, Rank::Ace)));
+        assert_eq!(pile.as_slice(), &[first, second]);
+
+        assert_eq!(pile.pop(), Some(second));
+        assert_eq!(pile.top(), Some(&first));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let ace = Card::new(Suit::Hearts, Rank::Ace);
+        let json = serde_json::to_string(&ace).unwrap();
+
+        assert_eq!(json, r#"{"suit":"Hearts","rank":"Ace"}"#);
+        assert_eq!(serde_json::from_str::<Card>(&json).unwrap(), ace);
+
+        let cards = deck().to_vec();
+        let json = serde_json::to_string(&cards).unwrap();
+
+        assert_eq!(serde_json::from_str::<Vec<Card>>(&json).unwrap(), cards);
     }
 }
@@ -0,0 +1,79 @@
+// a single error type for the crate's fallible operations
+
+use crate::card::standard::{Card, ParseCardError};
+use crate::game::IllegalAction;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardsError {
+    // the action is not currently legal, by its u32 encoding
+    IllegalAction(u32),
+    // the current player does not hold the card
+    CardNotHeld(Card),
+    // a draw from a deck with no cards left
+    DeckEmpty,
+    // a custom deck holds the card more than once
+    DuplicateCard(Card),
+    ParseError(ParseCardError),
+}
+
+impl std::fmt::Display for CardsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardsError::IllegalAction(action) => write!(f, "{}", IllegalAction(*action)),
+            CardsError::CardNotHeld(card) => write!(f, "{} is not in the current hand", card),
+            CardsError::DeckEmpty => write!(f, "the deck is empty"),
+            CardsError::DuplicateCard(card) => write!(f, "{} appears twice in the deck", card),
+            CardsError::ParseError(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for CardsError {}
+
+impl From<ParseCardError> for CardsError {
+    fn from(error: ParseCardError) -> Self {
+        CardsError::ParseError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::standard::{Deck, Rank, Suit};
+
+    #[test]
+    fn test_display() {
+        let card = Card::new(Suit::Hearts, Rank::Ace);
+
+        assert_eq!(CardsError::IllegalAction(7).to_string(), "illegal action 7");
+        assert_eq!(
+            CardsError::CardNotHeld(card).to_string(),
+            "Ace of Hearts is not in the current hand"
+        );
+        assert_eq!(CardsError::DeckEmpty.to_string(), "the deck is empty");
+        assert_eq!(
+            CardsError::DuplicateCard(card).to_string(),
+            "Ace of Hearts appears twice in the deck"
+        );
+
+        let parsed: Result<Card, CardsError> = "ZZ".parse::<Card>().map_err(CardsError::from);
+        assert_eq!(
+            parsed.unwrap_err().to_string(),
+            "cannot parse \"ZZ\" as a card"
+        );
+    }
+
+    #[test]
+    fn test_error() {
+        let error: Box<dyn std::error::Error> = Box::new(CardsError::DeckEmpty);
+        assert_eq!(error.to_string(), "the deck is empty");
+
+        let mut deck = Deck::from(Vec::new());
+        assert_eq!(deck.try_draw(), Err(CardsError::DeckEmpty));
+
+        assert_eq!(
+            CardsError::from(IllegalAction(3)),
+            CardsError::IllegalAction(3)
+        );
+    }
+}
@@ -1,16 +1,618 @@
+pub mod blackjack;
+pub mod crazyeights;
+pub mod gofish;
 pub mod highlow;
-// pub mod whist;
+pub mod redblack;
+pub mod tricktaking;
+pub mod war;
+pub mod whist;
+
+use crate::error::CardsError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionSpace {
+    // actions are the integers 0..n
+    Discrete(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObservationSpace {
+    // the state is one of n values
+    Discrete(usize),
+    // the state is a fixed number of components, each one of n values
+    MultiDiscrete(Vec<usize>),
+}
+
+// returned by a validating step when the action is not currently legal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IllegalAction(pub u32);
+
+impl std::fmt::Display for IllegalAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "illegal action {}", self.0)
+    }
+}
+
+impl std::error::Error for IllegalAction {}
+
+impl From<IllegalAction> for CardsError {
+    fn from(illegal: IllegalAction) -> Self {
+        CardsError::IllegalAction(illegal.0)
+    }
+}
+
+// a step's observation, reward and done with the legal actions that follow it
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepResult<S, R, A> {
+    pub state: S,
+    pub reward: R,
+    pub done: bool,
+    pub legal_actions: Vec<A>,
+}
 
 pub trait Game {
     type Action: Into<u32>;
     type Player: Into<u32>;
     type Reward: Into<f32>;
     type State;
+    // everything needed to restore the game but its rng
+    type Snapshot: Clone;
 
     fn current_player(&self) -> &Self::Player;
     fn legal_actions(&self) -> Vec<Self::Action>;
     fn observation(&self) -> Self::State;
     fn step(&mut self, action: Self::Action) -> (Self::State, Self::Reward, bool);
     fn reset(&mut self);
-    fn render(&self);
+    fn render_to(&self, w: &mut dyn std::io::Write) -> std::io::Result<()>;
+    fn action_space(&self) -> ActionSpace;
+    // the action encoded as `action` by Into<u32>, None outside the action space
+    fn action_from_u32(&self, action: u32) -> Option<Self::Action>;
+    fn observation_space(&self) -> ObservationSpace;
+    fn encode_observation(&self) -> Vec<f32>;
+    fn snapshot(&self) -> Self::Snapshot;
+    fn restore(&mut self, snapshot: Self::Snapshot);
+
+    // the smallest and largest reward a single step can give
+    fn reward_bounds(&self) -> (f32, f32) {
+        (0.0, 1.0)
+    }
+
+    // reseeds the game's rng so the next reset deals the same way for the same seed
+    fn seed(&mut self, _seed: u64) {}
+
+    // takes back the last step, false if the game cannot or has nothing to undo
+    fn undo(&mut self) -> bool {
+        false
+    }
+
+    // marks each action in the action space that is currently legal
+    fn legal_action_mask(&self) -> Vec<bool> {
+        let mut mask = match self.action_space() {
+            ActionSpace::Discrete(n) => vec![false; n],
+        };
+
+        for action in self.legal_actions() {
+            mask[action.into() as usize] = true;
+        }
+
+        mask
+    }
+
+    // render_to on stdout
+    fn render(&self) {
+        self.render_to(&mut std::io::stdout())
+            .expect("failed writing to stdout");
+    }
+
+    fn is_terminal(&self) -> bool {
+        false
+    }
+
+    fn num_players(&self) -> usize {
+        1
+    }
+
+    // the acting player's seat, for indexing per-player arrays
+    fn current_player_index(&self) -> usize {
+        0
+    }
+
+    // applies the actions in turn until they run out or the game is done
+    fn step_many(
+        &mut self,
+        actions: impl IntoIterator<Item = Self::Action>,
+    ) -> (Self::State, Vec<Self::Reward>, bool) {
+        let mut rewards = Vec::new();
+
+        for action in actions {
+            let (_, reward, done) = self.step(action);
+            rewards.push(reward);
+
+            if done {
+                return (self.observation(), rewards, true);
+            }
+        }
+
+        (self.observation(), rewards, self.is_terminal())
+    }
+
+    // as step, with the legal actions of the resulting state alongside
+    fn step_full(
+        &mut self,
+        action: Self::Action,
+    ) -> StepResult<Self::State, Self::Reward, Self::Action> {
+        let (state, reward, done) = self.step(action);
+
+        StepResult {
+            state,
+            reward,
+            done,
+            legal_actions: self.legal_actions(),
+        }
+    }
+
+    // the result of stepping a copy of the game, leaving this one untouched
+    fn peek(&self, action: Self::Action) -> (Self::State, Self::Reward, bool)
+    where
+        Self: Clone,
+    {
+        self.clone().step(action)
+    }
+
+    // lazily plays the game out with the policy, yielding each transition
+    fn play<F>(self, policy: F) -> Transitions<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self) -> Self::Action,
+    {
+        Transitions {
+            game: self,
+            policy,
+            done: false,
+        }
+    }
+}
+
+// iterator over (state, action, reward, done) until the game is done, see Game::play
+pub struct Transitions<G, F> {
+    game: G,
+    policy: F,
+    done: bool,
+}
+
+impl<G, F> Transitions<G, F> {
+    pub fn game(&self) -> &G {
+        &self.game
+    }
+}
+
+impl<G, F> Iterator for Transitions<G, F>
+where
+    G: Game,
+    G::Action: Clone,
+    F: FnMut(&G) -> G::Action,
+{
+    type Item = (G::State, G::Action, G::Reward, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let action = (self.policy)(&self.game);
+        let (state, reward, done) = self.game.step(action.clone());
+        self.done = done;
+
+        Some((state, action, reward, done))
+    }
+}
+
+// wraps a game to replace the reward emitted by step with a custom function
+// of the game after the step and its natural reward
+pub struct RewardShaping<G, F> {
+    game: G,
+    shaping: F,
+}
+
+impl<G, F> RewardShaping<G, F>
+where
+    G: Game,
+    F: Fn(&G, G::Reward) -> f32,
+{
+    pub fn new(game: G, shaping: F) -> Self {
+        RewardShaping { game, shaping }
+    }
+
+    pub fn inner(&self) -> &G {
+        &self.game
+    }
+}
+
+impl<G, F> Game for RewardShaping<G, F>
+where
+    G: Game,
+    F: Fn(&G, G::Reward) -> f32,
+{
+    type Action = G::Action;
+    type Player = G::Player;
+    type Reward = f32;
+    type State = G::State;
+    type Snapshot = G::Snapshot;
+
+    fn current_player(&self) -> &Self::Player {
+        self.game.current_player()
+    }
+
+    fn legal_actions(&self) -> Vec<Self::Action> {
+        self.game.legal_actions()
+    }
+
+    fn observation(&self) -> Self::State {
+        self.game.observation()
+    }
+
+    fn step(&mut self, action: Self::Action) -> (Self::State, Self::Reward, bool) {
+        let (state, reward, done) = self.game.step(action);
+        let reward = (self.shaping)(&self.game, reward);
+
+        (state, reward, done)
+    }
+
+    fn reset(&mut self) {
+        self.game.reset()
+    }
+
+    fn render_to(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        self.game.render_to(w)
+    }
+
+    fn action_space(&self) -> ActionSpace {
+        self.game.action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.game.observation_space()
+    }
+
+    fn encode_observation(&self) -> Vec<f32> {
+        self.game.encode_observation()
+    }
+
+    fn legal_action_mask(&self) -> Vec<bool> {
+        self.game.legal_action_mask()
+    }
+
+    fn action_from_u32(&self, action: u32) -> Option<Self::Action> {
+        self.game.action_from_u32(action)
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.game.seed(seed)
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.game.snapshot()
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        self.game.restore(snapshot)
+    }
+
+    // the wrapped game's natural bounds, the range of the shaped reward can't
+    // be known from an arbitrary function so these may not hold for it
+    fn reward_bounds(&self) -> (f32, f32) {
+        self.game.reward_bounds()
+    }
+
+    fn undo(&mut self) -> bool {
+        self.game.undo()
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.game.is_terminal()
+    }
+
+    fn num_players(&self) -> usize {
+        self.game.num_players()
+    }
+
+    fn current_player_index(&self) -> usize {
+        self.game.current_player_index()
+    }
+}
+
+// the totals from playing one game until it is done
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EpisodeResult {
+    pub total_reward: f32,
+    pub steps: usize,
+}
+
+// aggregate statistics over many episodes
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EpisodeStats {
+    pub episodes: usize,
+    pub mean_reward: f32,
+    pub min_reward: f32,
+    pub max_reward: f32,
+    pub mean_steps: f32,
+}
+
+// steps the game with the policy from its current state until it is done
+pub fn run_episode<G, P>(game: &mut G, mut policy: P) -> EpisodeResult
+where
+    G: Game,
+    P: FnMut(&G) -> G::Action,
+{
+    let mut result = EpisodeResult::default();
+
+    loop {
+        let action = policy(game);
+        let (_, reward, done) = game.step(action);
+
+        result.total_reward += reward.into();
+        result.steps += 1;
+
+        if done {
+            return result;
+        }
+    }
+}
+
+// resets and plays the game n times with the same policy
+pub fn run_episodes<G, P>(game: &mut G, n: usize, mut policy: P) -> EpisodeStats
+where
+    G: Game,
+    P: FnMut(&G) -> G::Action,
+{
+    if n == 0 {
+        return EpisodeStats::default();
+    }
+
+    let mut stats = EpisodeStats {
+        episodes: n,
+        min_reward: f32::INFINITY,
+        max_reward: f32::NEG_INFINITY,
+        ..EpisodeStats::default()
+    };
+
+    for _ in 0..n {
+        game.reset();
+        let result = run_episode(game, &mut policy);
+
+        stats.mean_reward += result.total_reward;
+        stats.mean_steps += result.steps as f32;
+        stats.min_reward = stats.min_reward.min(result.total_reward);
+        stats.max_reward = stats.max_reward.max(result.total_reward);
+    }
+
+    stats.mean_reward /= n as f32;
+    stats.mean_steps /= n as f32;
+
+    stats
+}
+
+// steps several copies of a game together, resetting each as it finishes
+pub struct VecEnv<G> {
+    envs: Vec<G>,
+}
+
+impl<G> VecEnv<G>
+where
+    G: Game + Clone,
+{
+    // clones the game n times, seeding each copy with its index so they deal differently
+    pub fn new(game: G, n: usize) -> Self {
+        let envs = (0..n)
+            .map(|i| {
+                let mut env = game.clone();
+                env.seed(i as u64);
+                env.reset();
+                env
+            })
+            .collect();
+
+        VecEnv { envs }
+    }
+
+    pub fn envs(&self) -> &[G] {
+        &self.envs
+    }
+
+    pub fn len(&self) -> usize {
+        self.envs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.envs.is_empty()
+    }
+
+    pub fn observations(&self) -> Vec<G::State> {
+        self.envs.iter().map(|env| env.observation()).collect()
+    }
+
+    // steps each game with its action, a finished game is reset and its
+    // first new observation returned alongside the final reward and done
+    pub fn step(&mut self, actions: &[G::Action]) -> Vec<(G::State, G::Reward, bool)>
+    where
+        G::Action: Clone,
+    {
+        assert_eq!(actions.len(), self.envs.len(), "one action per game");
+
+        self.envs
+            .iter_mut()
+            .zip(actions)
+            .map(|(env, action)| {
+                let (state, reward, done) = env.step(action.clone());
+
+                if !done {
+                    return (state, reward, done);
+                }
+
+                env.reset();
+                (env.observation(), reward, done)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::prelude::*;
+
+    use super::blackjack::Blackjack;
+    use super::highlow::{Action, HighLow};
+    use super::whist::Whist;
+    use super::*;
+
+    #[test]
+    fn test_play() {
+        let transitions: Vec<_> = HighLow::new().play(|_| Action::Higher).collect();
+
+        assert!(!transitions.is_empty());
+        assert!(transitions.len() <= 51);
+
+        let (_, _, _, done) = transitions.last().unwrap();
+        assert!(*done);
+        assert!(transitions[..transitions.len() - 1]
+            .iter()
+            .all(|(_, _, _, done)| !done));
+    }
+
+    #[test]
+    fn test_legal_action_mask() {
+        assert_eq!(HighLow::new().legal_action_mask(), vec![true; 4]);
+
+        let mut whist = Whist::with_seed(37);
+
+        for _ in 0..6 {
+            let mask = whist.legal_action_mask();
+            let actions = whist.legal_actions();
+
+            assert_eq!(mask.len(), 13);
+            assert_eq!(mask.iter().filter(|legal| **legal).count(), actions.len());
+            assert!(actions.iter().all(|action| mask[*action as usize]));
+
+            whist.try_step(actions[0]).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_seed() {
+        // different entropy before seeding, the same deal after
+        let (mut first, mut second) = (Whist::new(), Whist::new());
+        first.seed(11);
+        second.seed(11);
+        first.reset();
+        second.reset();
+
+        // every hand, not just the trumps and the current player's view
+        assert_eq!(first.full_state(), second.full_state());
+
+        let (mut first, mut second) = (Blackjack::new(), Blackjack::new());
+        first.seed(11);
+        second.seed(11);
+        first.reset();
+        second.reset();
+
+        assert_eq!(first.observation(), second.observation());
+    }
+
+    #[test]
+    fn test_run_episodes() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        let mut highlow = HighLow::new();
+        highlow.seed(3);
+
+        let stats = run_episodes(&mut highlow, 100, |game: &HighLow| {
+            *game.legal_actions().choose(&mut rng).unwrap()
+        });
+
+        assert_eq!(stats.episodes, 100);
+        assert!(stats.mean_steps >= 1.0 && stats.mean_steps <= 51.0);
+        assert!(stats.min_reward >= 0.0);
+        assert!(stats.min_reward <= stats.mean_reward);
+        assert!(stats.mean_reward <= stats.max_reward);
+        assert!(stats.max_reward > 0.0);
+
+        // every reset deals a full hand of 52 plays
+        let stats = run_episodes(&mut Whist::new(), 3, |game: &Whist| game.legal_actions()[0]);
+
+        assert_eq!(stats.mean_steps, 52.0);
+    }
+
+    #[test]
+    fn test_vec_env() {
+        let mut rng = SmallRng::seed_from_u64(8);
+        let mut envs = VecEnv::new(HighLow::new(), 8);
+
+        assert_eq!(envs.len(), 8);
+
+        let mut finished = [false; 8];
+
+        // a game of HighLow lasts at most 51 guesses
+        for _ in 0..60 {
+            let actions: Vec<_> = envs
+                .envs()
+                .iter()
+                .map(|env| *env.legal_actions().choose(&mut rng).unwrap())
+                .collect();
+
+            for (i, (state, _, done)) in envs.step(&actions).into_iter().enumerate() {
+                let env = &envs.envs()[i];
+                assert_eq!(state, env.observation());
+
+                if done {
+                    finished[i] = true;
+                    assert!(!env.is_terminal());
+                    assert_eq!(env.score(), 0);
+                }
+            }
+        }
+
+        assert!(finished.iter().all(|done| *done));
+    }
+
+    #[test]
+    fn test_action_from_u32() {
+        let highlow = HighLow::new();
+
+        for action in highlow.legal_actions() {
+            assert!(highlow.action_from_u32(action.into()) == Some(action));
+        }
+
+        assert!(highlow.action_from_u32(4).is_none());
+
+        let mut whist = Whist::with_seed(71);
+
+        for _ in 0..20 {
+            for action in whist.legal_actions() {
+                assert_eq!(whist.action_from_u32(action.into()), Some(action));
+            }
+
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        }
+
+        assert_eq!(whist.action_from_u32(13), None);
+    }
+
+    #[test]
+    fn test_step_full() {
+        let mut whist = Whist::with_seed(97);
+
+        while !whist.is_terminal() {
+            let action = whist.legal_actions()[0];
+            let result = whist.step_full(action);
+
+            assert_eq!(result.state, whist.observation());
+            assert_eq!(result.legal_actions, whist.legal_actions());
+            assert_eq!(result.done, whist.is_terminal());
+        }
+
+        let mut highlow = HighLow::new();
+        let result = highlow.step_full(Action::Lower);
+
+        assert!(result.legal_actions == highlow.legal_actions());
+        assert_eq!(result.state, highlow.observation());
+    }
 }
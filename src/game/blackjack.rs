@@ -0,0 +1,351 @@
+// a 1 player game of Blackjack against a dealer who hits to 17
+
+use std::cmp::Ordering;
+
+use crate::game::{ActionSpace, Game, ObservationSpace};
+
+use rand::prelude::*;
+
+use crate::card::standard::{Card, Deck, Rank};
+
+const DEALER_STANDS: u8 = 17;
+
+// the best total of a hand, counting aces as 11 unless that would bust
+pub fn hand_total(cards: &[Card]) -> u8 {
+    let mut total = 0;
+    let mut soft_aces = 0;
+
+    for card in cards {
+        total += match card.rank {
+            Rank::Ace => {
+                soft_aces += 1;
+                11
+            }
+            Rank::Jack | Rank::Queen | Rank::King => 10,
+            rank => rank.value(),
+        };
+    }
+
+    while total > 21 && soft_aces > 0 {
+        total -= 10;
+        soft_aces -= 1;
+    }
+
+    total
+}
+
+#[derive(Clone)]
+pub struct Blackjack {
+    deck: Deck,
+    player: Vec<Card>,
+    dealer: Vec<Card>,
+    done: bool,
+    rng: SmallRng,
+}
+
+impl Blackjack {
+    pub fn new() -> Self {
+        Blackjack::from_rng(SmallRng::from_entropy())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        Blackjack::from_rng(SmallRng::seed_from_u64(seed))
+    }
+
+    fn from_rng(rng: SmallRng) -> Self {
+        let mut blackjack = Blackjack {
+            deck: Deck::new(),
+            player: Vec::new(),
+            dealer: Vec::new(),
+            done: false,
+            rng,
+        };
+
+        blackjack.deal();
+        blackjack
+    }
+
+    fn deal(&mut self) {
+        self.deck = Deck::new();
+        self.deck.shuffle(&mut self.rng);
+
+        self.player = vec![self.deck.draw().unwrap(), self.deck.draw().unwrap()];
+        self.dealer = vec![self.deck.draw().unwrap(), self.deck.draw().unwrap()];
+        self.done = false;
+    }
+
+    pub fn player_total(&self) -> u8 {
+        hand_total(&self.player)
+    }
+
+    pub fn dealer_total(&self) -> u8 {
+        hand_total(&self.dealer)
+    }
+
+    fn is_natural(cards: &[Card]) -> bool {
+        cards.len() == 2 && hand_total(cards) == 21
+    }
+
+    // plays out the dealer's hand and settles the bet
+    fn settle(&mut self) -> i8 {
+        self.done = true;
+
+        match (
+            Blackjack::is_natural(&self.player),
+            Blackjack::is_natural(&self.dealer),
+        ) {
+            (true, true) => return 0,
+            (true, false) => return 1,
+            (false, true) => return -1,
+            (false, false) => {}
+        }
+
+        while self.dealer_total() < DEALER_STANDS {
+            match self.deck.draw() {
+                Some(card) => self.dealer.push(card),
+                None => break,
+            }
+        }
+
+        if self.dealer_total() > 21 {
+            return 1;
+        }
+
+        match self.player_total().cmp(&self.dealer_total()) {
+            Ordering::Greater => 1,
+            Ordering::Equal => 0,
+            Ordering::Less => -1,
+        }
+    }
+
+    // the whole game rather than the observation
+    pub fn full_state(&self) -> FullState {
+        FullState {
+            player: self.player.clone(),
+            dealer: self.dealer.clone(),
+            deck: self.deck.iter().rev().copied().collect(),
+            done: self.done,
+        }
+    }
+}
+
+// everything about the game, the dealer's hole card included, for debugging and replays
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullState {
+    pub player: Vec<Card>,
+    pub dealer: Vec<Card>,
+    // the cards left in the order they will be drawn
+    pub deck: Vec<Card>,
+    pub done: bool,
+}
+
+impl Default for Blackjack {
+    fn default() -> Self {
+        Blackjack::new()
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum Action {
+    Hit,
+    Stand,
+}
+
+impl From<u32> for Action {
+    fn from(action: u32) -> Self {
+        match action {
+            0 => Action::Hit,
+            1 => Action::Stand,
+            _ => panic!("Invalid action"),
+        }
+    }
+}
+
+impl From<Action> for u32 {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::Hit => 0,
+            Action::Stand => 1,
+        }
+    }
+}
+
+impl Game for Blackjack {
+    type Action = Action;
+    type Player = u8;
+    // +1 for a win, 0 for a push, -1 for a loss, given when the hand is settled
+    type Reward = i8;
+    // the player's total and the dealer's up-card
+    type State = (u8, Card);
+    type Snapshot = FullState;
+
+    fn current_player(&self) -> &Self::Player {
+        &1
+    }
+
+    fn legal_actions(&self) -> Vec<Self::Action> {
+        vec![Action::Hit, Action::Stand]
+    }
+
+    fn observation(&self) -> Self::State {
+        (self.player_total(), self.dealer[0])
+    }
+
+    fn step(&mut self, action: Self::Action) -> (Self::State, Self::Reward, bool) {
+        if self.done {
+            return (self.observation(), 0, true);
+        }
+
+        // a natural stands automatically
+        if action == Action::Stand || Blackjack::is_natural(&self.player) {
+            let reward = self.settle();
+            return (self.observation(), reward, true);
+        }
+
+        if let Some(card) = self.deck.draw() {
+            self.player.push(card);
+        }
+
+        if self.player_total() > 21 {
+            self.done = true;
+            return (self.observation(), -1, true);
+        }
+
+        (self.observation(), 0, false)
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    fn reset(&mut self) {
+        self.deal();
+    }
+
+    fn action_space(&self) -> ActionSpace {
+        ActionSpace::Discrete(2)
+    }
+
+    fn action_from_u32(&self, action: u32) -> Option<Self::Action> {
+        (action < 2).then(|| Action::from(action))
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.full_state()
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        self.player = snapshot.player;
+        self.dealer = snapshot.dealer;
+        self.deck = Deck::from_cards(&snapshot.deck);
+        self.done = snapshot.done;
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        // a total of at most 31 and the up-card as its u32 encoding
+        ObservationSpace::MultiDiscrete(vec![32, 52])
+    }
+
+    fn encode_observation(&self) -> Vec<f32> {
+        // [0] the player's total scaled by 21, [1, 53) one-hot of the up-card
+        let mut encoded = vec![0.0; 53];
+        encoded[0] = self.player_total() as f32 / 21.0;
+        encoded[1 + u32::from(self.dealer[0]) as usize] = 1.0;
+        encoded
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.done
+    }
+
+    fn reward_bounds(&self) -> (f32, f32) {
+        (-1.0, 1.0)
+    }
+
+    fn num_players(&self) -> usize {
+        1
+    }
+
+    fn render_to(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        writeln!(w, "Player: {:?} ({})", self.player, self.player_total())?;
+        writeln!(w, "Dealer shows: {}", self.dealer[0])?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::standard::Suit;
+
+    fn blackjack(player: &[Rank], dealer: &[Rank], deck: &[Rank]) -> Blackjack {
+        let cards = |ranks: &[Rank], suit| -> Vec<Card> {
+            ranks.iter().map(|rank| Card::new(suit, *rank)).collect()
+        };
+
+        let mut blackjack = Blackjack::with_seed(0);
+        blackjack.player = cards(player, Suit::Hearts);
+        blackjack.dealer = cards(dealer, Suit::Spades);
+        blackjack.deck = Deck::from(cards(deck, Suit::Clubs));
+        blackjack
+    }
+
+    #[test]
+    fn test_hand_total() {
+        let hand = |ranks: &[Rank]| -> Vec<Card> {
+            ranks
+                .iter()
+                .map(|rank| Card::new(Suit::Hearts, *rank))
+                .collect()
+        };
+
+        assert_eq!(hand_total(&hand(&[Rank::Ace, Rank::King])), 21);
+        assert_eq!(hand_total(&hand(&[Rank::Ace, Rank::Ace])), 12);
+        assert_eq!(hand_total(&hand(&[Rank::Ace, Rank::Nine, Rank::Five])), 15);
+        assert_eq!(hand_total(&hand(&[Rank::Two, Rank::Queen])), 12);
+    }
+
+    #[test]
+    fn test_natural() {
+        let mut blackjack = blackjack(&[Rank::Ace, Rank::King], &[Rank::Nine, Rank::Seven], &[]);
+
+        let ((total, _), reward, done) = blackjack.step(Action::Hit);
+
+        assert_eq!(total, 21);
+        assert_eq!(reward, 1);
+        assert!(done);
+        assert_eq!(blackjack.dealer.len(), 2);
+    }
+
+    #[test]
+    fn test_bust() {
+        let mut blackjack = blackjack(
+            &[Rank::King, Rank::Six],
+            &[Rank::Nine, Rank::Seven],
+            &[Rank::Nine],
+        );
+
+        let ((total, _), reward, done) = blackjack.step(Action::Hit);
+
+        assert_eq!(total, 25);
+        assert_eq!(reward, -1);
+        assert!(done);
+        assert!(blackjack.is_terminal());
+    }
+
+    #[test]
+    fn test_dealer_bust() {
+        let mut blackjack = blackjack(
+            &[Rank::King, Rank::Eight],
+            &[Rank::King, Rank::Six],
+            &[Rank::Two, Rank::Queen],
+        );
+
+        let (_, reward, done) = blackjack.step(Action::Stand);
+
+        assert_eq!(blackjack.dealer_total(), 26);
+        assert_eq!(reward, 1);
+        assert!(done);
+    }
+}
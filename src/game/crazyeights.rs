@@ -0,0 +1,499 @@
+// Crazy Eights for 2 to 4 players, matching the top discard by suit or rank with eights wild
+
+use crate::game::{ActionSpace, Game, ObservationSpace};
+
+use rand::prelude::*;
+
+use crate::card::standard::{Card, Deck, Pile, Rank, Suit};
+
+#[derive(Clone)]
+pub struct CrazyEights {
+    // indexed by player id
+    hands: Vec<Vec<Card>>,
+    stock: Deck,
+    discard: Pile,
+    // the suit to match, the declared suit after an eight
+    suit: Suit,
+    turn: u8,
+    // players in a row who could neither play nor draw
+    passes: usize,
+    winner: Option<u8>,
+    rng: SmallRng,
+}
+
+impl CrazyEights {
+    pub fn new() -> Self {
+        CrazyEights::with_players(2)
+    }
+
+    pub fn with_players(players: usize) -> Self {
+        CrazyEights::from_rng(SmallRng::from_entropy(), players)
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        CrazyEights::from_rng(SmallRng::seed_from_u64(seed), 2)
+    }
+
+    fn from_rng(rng: SmallRng, players: usize) -> Self {
+        assert!(
+            (2..=4).contains(&players),
+            "Crazy Eights is played by two to four players"
+        );
+
+        let mut crazyeights = CrazyEights {
+            hands: vec![Vec::new(); players],
+            stock: Deck::from(Vec::new()),
+            discard: Pile::new(),
+            suit: Suit::Hearts,
+            turn: 0,
+            passes: 0,
+            winner: None,
+            rng,
+        };
+
+        crazyeights.deal();
+        crazyeights
+    }
+
+    // 7 cards each for two players and 5 for more, then the top card starts the discard
+    fn deal(&mut self) {
+        self.stock = Deck::new();
+        self.stock.shuffle(&mut self.rng);
+
+        let players = self.hands.len();
+        let hand_size = if players == 2 { 7 } else { 5 };
+
+        self.hands = vec![Vec::new(); players];
+
+        for _ in 0..hand_size {
+            for hand in self.hands.iter_mut() {
+                hand.extend(self.stock.draw());
+            }
+        }
+
+        let top = self.stock.draw().unwrap();
+
+        self.discard = Pile::new();
+        self.discard.push(top);
+        self.suit = top.suit;
+        self.turn = 0;
+        self.passes = 0;
+        self.winner = None;
+    }
+
+    pub fn top(&self) -> Card {
+        *self.discard.top().unwrap()
+    }
+
+    pub fn suit(&self) -> Suit {
+        self.suit
+    }
+
+    pub fn winner(&self) -> Option<u8> {
+        self.winner
+    }
+
+    fn matches(&self, card: &Card) -> bool {
+        card.rank == Rank::Eight || card.is_of_suit(self.suit) || card.rank == self.top().rank
+    }
+
+    fn next_turn(&mut self) {
+        self.turn = (self.turn + 1) % self.hands.len() as u8;
+    }
+
+    // the whole game rather than the current player's observation
+    pub fn full_state(&self) -> FullState {
+        FullState {
+            hands: self.hands.clone(),
+            stock: self.stock.iter().rev().copied().collect(),
+            discard: self.discard.as_slice().to_vec(),
+            suit: self.suit,
+            turn: self.turn,
+            passes: self.passes,
+            winner: self.winner,
+        }
+    }
+}
+
+// everything about the game, every hand included, for debugging and replays
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullState {
+    pub hands: Vec<Vec<Card>>,
+    // the cards left in the order they will be drawn
+    pub stock: Vec<Card>,
+    // from the first card put down to the top
+    pub discard: Vec<Card>,
+    pub suit: Suit,
+    pub turn: u8,
+    pub passes: usize,
+    pub winner: Option<u8>,
+}
+
+impl Default for CrazyEights {
+    fn default() -> Self {
+        CrazyEights::new()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Action {
+    // the card at `index` in the hand, an eight also declares the suit to match next
+    Play { index: u8, declared: Option<Suit> },
+    // only when no card can be played, passing if the stock is empty
+    Draw,
+}
+
+// a hand never holds more than the whole deck
+const MAX_INDEX: u32 = 52;
+
+impl From<u32> for Action {
+    fn from(action: u32) -> Self {
+        // five actions per hand index, a declared suit in suit order or none
+        match action {
+            action if action < MAX_INDEX * 5 => Action::Play {
+                index: (action / 5) as u8,
+                declared: Suit::all().get((action % 5) as usize).copied(),
+            },
+            action if action == MAX_INDEX * 5 => Action::Draw,
+            _ => panic!("Invalid action"),
+        }
+    }
+}
+
+impl From<Action> for u32 {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::Play { index, declared } => {
+                index as u32 * 5 + declared.map_or(4, |suit| suit as u32)
+            }
+            Action::Draw => MAX_INDEX * 5,
+        }
+    }
+}
+
+impl Game for CrazyEights {
+    type Action = Action;
+    type Player = u8;
+    // 1 for the player who goes out
+    type Reward = u8;
+    // the current player's hand, the top discard, the suit to match and the
+    // number of cards every player holds by id
+    type State = (Vec<Card>, Card, Suit, Vec<usize>);
+    type Snapshot = FullState;
+
+    fn current_player(&self) -> &Self::Player {
+        &self.turn
+    }
+
+    fn legal_actions(&self) -> Vec<Self::Action> {
+        if self.is_terminal() {
+            return Vec::new();
+        }
+
+        let mut actions = Vec::new();
+
+        for (index, card) in self.hands[self.turn as usize].iter().enumerate() {
+            let index = index as u8;
+
+            if card.rank == Rank::Eight {
+                actions.extend(Suit::iter().map(|suit| Action::Play {
+                    index,
+                    declared: Some(suit),
+                }));
+            } else if self.matches(card) {
+                actions.push(Action::Play {
+                    index,
+                    declared: None,
+                });
+            }
+        }
+
+        if actions.is_empty() {
+            actions.push(Action::Draw);
+        }
+
+        actions
+    }
+
+    fn observation(&self) -> Self::State {
+        (
+            self.hands[self.turn as usize].clone(),
+            self.top(),
+            self.suit,
+            self.hands.iter().map(|hand| hand.len()).collect(),
+        )
+    }
+
+    fn step(&mut self, action: Self::Action) -> (Self::State, Self::Reward, bool) {
+        if self.is_terminal() {
+            return (self.observation(), 0, true);
+        }
+
+        let (index, declared) = match action {
+            Action::Play { index, declared } => (index as usize, declared),
+            Action::Draw => {
+                // the drawer keeps the turn until they can play
+                match self.stock.draw() {
+                    Some(card) => {
+                        self.hands[self.turn as usize].push(card);
+                        self.passes = 0;
+                    }
+                    None => {
+                        self.passes += 1;
+                        self.next_turn();
+                    }
+                }
+
+                return (self.observation(), 0, self.is_terminal());
+            }
+        };
+
+        let card = self.hands[self.turn as usize].remove(index);
+
+        self.discard.push(card);
+        self.suit = match card.rank {
+            Rank::Eight => declared.unwrap_or(card.suit),
+            _ => card.suit,
+        };
+        self.passes = 0;
+
+        if self.hands[self.turn as usize].is_empty() {
+            self.winner = Some(self.turn);
+            return (self.observation(), 1, true);
+        }
+
+        self.next_turn();
+
+        (self.observation(), 0, false)
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    fn reset(&mut self) {
+        self.deal();
+    }
+
+    fn action_space(&self) -> ActionSpace {
+        ActionSpace::Discrete(MAX_INDEX as usize * 5 + 1)
+    }
+
+    fn action_from_u32(&self, action: u32) -> Option<Self::Action> {
+        (action <= MAX_INDEX * 5).then(|| Action::from(action))
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.full_state()
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        self.hands = snapshot.hands;
+        self.stock = Deck::from_cards(&snapshot.stock);
+        self.discard = Pile::new();
+
+        for card in snapshot.discard {
+            self.discard.push(card);
+        }
+
+        self.suit = snapshot.suit;
+        self.turn = snapshot.turn;
+        self.passes = snapshot.passes;
+        self.winner = snapshot.winner;
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        // whether each card is in hand, the top discard as its u32 encoding,
+        // the suit to match and each player's hand size
+        let mut space = vec![2; 52];
+        space.push(52);
+        space.push(4);
+        space.extend(vec![53; self.num_players()]);
+
+        ObservationSpace::MultiDiscrete(space)
+    }
+
+    fn encode_observation(&self) -> Vec<f32> {
+        // [0, 52) hand mask, [52, 104) one-hot of the top discard, [104, 108)
+        // one-hot of the suit to match, then each hand size out of 52 by id
+        let mut encoded = vec![0.0; 108];
+
+        for card in self.hands[self.turn as usize].iter() {
+            encoded[u32::from(*card) as usize] = 1.0;
+        }
+
+        encoded[52 + u32::from(self.top()) as usize] = 1.0;
+        encoded[104 + self.suit as usize] = 1.0;
+
+        encoded.extend(self.hands.iter().map(|hand| hand.len() as f32 / 52.0));
+        encoded
+    }
+
+    // someone went out, or every player in turn could neither play nor draw
+    fn is_terminal(&self) -> bool {
+        self.winner.is_some() || self.passes >= self.hands.len()
+    }
+
+    fn num_players(&self) -> usize {
+        self.hands.len()
+    }
+
+    fn current_player_index(&self) -> usize {
+        self.turn as usize
+    }
+
+    fn render_to(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        writeln!(w, "Top: {} ({} to match)", self.top(), self.suit)?;
+        writeln!(
+            w,
+            "Player {} hand: {:?}",
+            self.turn, self.hands[self.turn as usize]
+        )?;
+        writeln!(w, "Stock: {} cards", self.stock.remaining())?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crazyeights(hands: &[&[&str]], top: &str, stock: &[&str]) -> CrazyEights {
+        let cards = |cards: &[&str]| -> Vec<Card> {
+            cards.iter().map(|card| card.parse().unwrap()).collect()
+        };
+
+        let mut crazyeights = CrazyEights::with_seed(0);
+        let top: Card = top.parse().unwrap();
+
+        crazyeights.hands = hands.iter().map(|hand| cards(hand)).collect();
+        crazyeights.stock = Deck::from_cards(&cards(stock));
+        crazyeights.discard = Pile::new();
+        crazyeights.discard.push(top);
+        crazyeights.suit = top.suit;
+        crazyeights
+    }
+
+    #[test]
+    fn test_deal() {
+        let crazyeights = CrazyEights::with_seed(1);
+
+        assert!(crazyeights.hands.iter().all(|hand| hand.len() == 7));
+        assert_eq!(crazyeights.stock.remaining(), 52 - 14 - 1);
+        assert_eq!(crazyeights.suit(), crazyeights.top().suit);
+
+        assert!(CrazyEights::with_players(4)
+            .hands
+            .iter()
+            .all(|hand| hand.len() == 5));
+    }
+
+    #[test]
+    fn test_match() {
+        let mut crazyeights = crazyeights(&[&["5C", "9H", "KS"], &["2D"]], "5H", &["3C"]);
+
+        let play = |index| Action::Play {
+            index,
+            declared: None,
+        };
+
+        // the five matches by rank and the nine by suit
+        assert_eq!(crazyeights.legal_actions(), vec![play(0), play(1)]);
+
+        let ((_, top, suit, sizes), reward, done) = crazyeights.step(play(0));
+
+        assert_eq!(top, "5C".parse().unwrap());
+        assert_eq!(suit, Suit::Clubs);
+        assert_eq!(sizes, vec![2, 1]);
+        assert_eq!(reward, 0);
+        assert!(!done);
+        assert_eq!(*crazyeights.current_player(), 1);
+    }
+
+    #[test]
+    fn test_eight() {
+        let mut crazyeights = crazyeights(&[&["8S", "2C"], &["4D", "4H"]], "KH", &["3C"]);
+
+        // an eight is wild and may declare any suit
+        assert_eq!(crazyeights.legal_actions().len(), 4);
+
+        crazyeights.step(Action::Play {
+            index: 0,
+            declared: Some(Suit::Diamonds),
+        });
+
+        assert_eq!(crazyeights.suit(), Suit::Diamonds);
+        assert_eq!(
+            crazyeights.legal_actions(),
+            vec![Action::Play {
+                index: 0,
+                declared: None,
+            }]
+        );
+
+        let action = Action::Play {
+            index: 3,
+            declared: Some(Suit::Spades),
+        };
+        assert_eq!(Action::from(u32::from(action)), action);
+        assert_eq!(Action::from(u32::from(Action::Draw)), Action::Draw);
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut crazyeights = crazyeights(&[&["2C", "3D"], &["4S"]], "KH", &["9S", "QH"]);
+
+        assert_eq!(crazyeights.legal_actions(), vec![Action::Draw]);
+
+        // the nine cannot be played either, so the drawer draws again
+        crazyeights.step(Action::Draw);
+        assert_eq!(*crazyeights.current_player(), 0);
+        assert_eq!(crazyeights.legal_actions(), vec![Action::Draw]);
+
+        crazyeights.step(Action::Draw);
+        assert_eq!(crazyeights.stock.remaining(), 0);
+        assert_eq!(crazyeights.hands[0].len(), 4);
+
+        // the queen of hearts can be played
+        assert_eq!(
+            crazyeights.legal_actions(),
+            vec![Action::Play {
+                index: 3,
+                declared: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_go_out() {
+        let mut crazyeights = crazyeights(&[&["2H"], &["4S"]], "KH", &[]);
+
+        let (_, reward, done) = crazyeights.step(Action::Play {
+            index: 0,
+            declared: None,
+        });
+
+        assert_eq!(reward, 1);
+        assert!(done);
+        assert_eq!(crazyeights.winner(), Some(0));
+        assert!(crazyeights.legal_actions().is_empty());
+    }
+
+    #[test]
+    fn test_play_through() {
+        for seed in 0..10 {
+            let mut crazyeights = CrazyEights::with_seed(seed);
+            let mut steps = 0;
+
+            while !crazyeights.is_terminal() {
+                crazyeights.step(crazyeights.legal_actions()[0]);
+                steps += 1;
+                assert!(steps < 1000);
+            }
+
+            let encoded = crazyeights.encode_observation();
+            assert_eq!(encoded.len(), 110);
+        }
+    }
+}
@@ -0,0 +1,402 @@
+// 2 player Go Fish, asking the other player for a rank and collecting books of four
+
+use crate::game::{ActionSpace, Game, ObservationSpace};
+
+use rand::prelude::*;
+
+use crate::card::standard::{Card, Deck, Rank};
+
+const HAND_SIZE: usize = 7;
+
+#[derive(Clone)]
+pub struct GoFish {
+    hands: [Vec<Card>; 2],
+    pool: Deck,
+    books: [Vec<Rank>; 2],
+    // the player asking, 0 or 1
+    turn: u8,
+    rng: SmallRng,
+}
+
+impl GoFish {
+    pub fn new() -> Self {
+        GoFish::from_rng(SmallRng::from_entropy())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        GoFish::from_rng(SmallRng::seed_from_u64(seed))
+    }
+
+    fn from_rng(rng: SmallRng) -> Self {
+        let mut gofish = GoFish {
+            hands: [Vec::new(), Vec::new()],
+            pool: Deck::from(Vec::new()),
+            books: [Vec::new(), Vec::new()],
+            turn: 0,
+            rng,
+        };
+
+        gofish.deal();
+        gofish
+    }
+
+    fn deal(&mut self) {
+        self.pool = Deck::new();
+        self.pool.shuffle(&mut self.rng);
+
+        self.hands = [Vec::new(), Vec::new()];
+        self.books = [Vec::new(), Vec::new()];
+        self.turn = 0;
+
+        for _ in 0..HAND_SIZE {
+            for hand in self.hands.iter_mut() {
+                hand.extend(self.pool.draw());
+            }
+        }
+    }
+
+    // books completed by each player
+    pub fn books(&self) -> [usize; 2] {
+        [self.books[0].len(), self.books[1].len()]
+    }
+
+    // moves any complete set of four from the player's hand to their books
+    fn collect_books(&mut self, player: usize) -> u8 {
+        let mut collected = 0;
+
+        for rank in Rank::all() {
+            let hand = &mut self.hands[player];
+
+            if hand.iter().filter(|card| card.rank == rank).count() == 4 {
+                hand.retain(|card| card.rank != rank);
+                self.books[player].push(rank);
+                collected += 1;
+            }
+        }
+
+        collected
+    }
+
+    // a player with an empty hand draws from the pool, or passes if it is empty too
+    fn refill(&mut self) {
+        for _ in 0..2 {
+            let turn = self.turn as usize;
+
+            if !self.hands[turn].is_empty() {
+                return;
+            }
+
+            match self.pool.draw() {
+                Some(card) => self.hands[turn].push(card),
+                None => self.turn = 1 - self.turn,
+            }
+        }
+    }
+
+    // the whole game rather than the asking player's observation
+    pub fn full_state(&self) -> FullState {
+        FullState {
+            hands: self.hands.clone(),
+            pool: self.pool.iter().rev().copied().collect(),
+            books: self.books.clone(),
+            turn: self.turn,
+        }
+    }
+}
+
+// everything about the game, both hands included, for debugging and replays
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullState {
+    pub hands: [Vec<Card>; 2],
+    // the cards left in the order they will be drawn
+    pub pool: Vec<Card>,
+    pub books: [Vec<Rank>; 2],
+    pub turn: u8,
+}
+
+impl Default for GoFish {
+    fn default() -> Self {
+        GoFish::new()
+    }
+}
+
+// the rank asked for
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Action(pub Rank);
+
+impl From<u32> for Action {
+    fn from(action: u32) -> Self {
+        match Rank::all().get(action as usize) {
+            Some(rank) => Action(*rank),
+            None => panic!("Invalid action"),
+        }
+    }
+}
+
+impl From<Action> for u32 {
+    fn from(action: Action) -> Self {
+        Rank::all()
+            .iter()
+            .position(|rank| *rank == action.0)
+            .unwrap() as u32
+    }
+}
+
+impl Game for GoFish {
+    type Action = Action;
+    type Player = u8;
+    // books completed by the asking player on this step
+    type Reward = u8;
+    // the asking player's hand, the books of each player, the cards the
+    // other player holds and the cards left in the pool
+    type State = (Vec<Card>, [usize; 2], usize, usize);
+    type Snapshot = FullState;
+
+    fn current_player(&self) -> &Self::Player {
+        &self.turn
+    }
+
+    fn legal_actions(&self) -> Vec<Self::Action> {
+        // a player may only ask for a rank they hold
+        let hand = &self.hands[self.turn as usize];
+
+        Rank::all()
+            .into_iter()
+            .filter(|rank| hand.iter().any(|card| card.rank == *rank))
+            .map(Action)
+            .collect()
+    }
+
+    fn observation(&self) -> Self::State {
+        let turn = self.turn as usize;
+
+        (
+            self.hands[turn].clone(),
+            self.books(),
+            self.hands[1 - turn].len(),
+            self.pool.remaining(),
+        )
+    }
+
+    fn step(&mut self, action: Self::Action) -> (Self::State, Self::Reward, bool) {
+        if self.is_terminal() {
+            return (self.observation(), 0, true);
+        }
+
+        let asker = self.turn as usize;
+        let Action(rank) = action;
+
+        let other = &mut self.hands[1 - asker];
+        let taken: Vec<_> = other
+            .iter()
+            .filter(|card| card.rank == rank)
+            .copied()
+            .collect();
+        other.retain(|card| card.rank != rank);
+
+        // the asker goes again after taking cards, or fishing the rank they asked for
+        let again = if taken.is_empty() {
+            match self.pool.draw() {
+                Some(card) => {
+                    self.hands[asker].push(card);
+                    card.rank == rank
+                }
+                None => false,
+            }
+        } else {
+            self.hands[asker].extend(taken);
+            true
+        };
+
+        let reward = self.collect_books(asker);
+
+        if !again {
+            self.turn = 1 - self.turn;
+        }
+
+        self.refill();
+
+        (self.observation(), reward, self.is_terminal())
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    fn reset(&mut self) {
+        self.deal();
+    }
+
+    fn action_space(&self) -> ActionSpace {
+        // a rank in Rank::all order
+        ActionSpace::Discrete(13)
+    }
+
+    fn action_from_u32(&self, action: u32) -> Option<Self::Action> {
+        Rank::all().get(action as usize).copied().map(Action)
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.full_state()
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        self.hands = snapshot.hands;
+        self.pool = Deck::from_cards(&snapshot.pool);
+        self.books = snapshot.books;
+        self.turn = snapshot.turn;
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        // whether each card is in hand, each player's books, the other
+        // player's hand size and the cards left in the pool
+        let mut space = vec![2; 52];
+        space.extend([14, 14, 53, 53]);
+
+        ObservationSpace::MultiDiscrete(space)
+    }
+
+    fn encode_observation(&self) -> Vec<f32> {
+        // [0, 52) hand mask, [52, 54) books of the asking player then the
+        // other, both out of 13, [54, 56) other hand and pool out of 52
+        let turn = self.turn as usize;
+        let mut encoded = vec![0.0; 56];
+
+        for card in self.hands[turn].iter() {
+            encoded[u32::from(*card) as usize] = 1.0;
+        }
+
+        encoded[52] = self.books[turn].len() as f32 / 13.0;
+        encoded[53] = self.books[1 - turn].len() as f32 / 13.0;
+        encoded[54] = self.hands[1 - turn].len() as f32 / 52.0;
+        encoded[55] = self.pool.remaining() as f32 / 52.0;
+
+        encoded
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.books[0].len() + self.books[1].len() == 13
+    }
+
+    fn num_players(&self) -> usize {
+        2
+    }
+
+    fn current_player_index(&self) -> usize {
+        self.turn as usize
+    }
+
+    fn reward_bounds(&self) -> (f32, f32) {
+        // the asked rank and a different fished rank can both complete
+        (0.0, 2.0)
+    }
+
+    fn render_to(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        let turn = self.turn as usize;
+
+        writeln!(w, "Player {} hand: {:?}", turn, self.hands[turn])?;
+        writeln!(w, "Books: {:?}", self.books)?;
+        writeln!(w, "Pool: {} cards", self.pool.remaining())?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::standard::Suit;
+
+    fn gofish(hands: [&[&str]; 2], pool: &[&str]) -> GoFish {
+        let cards = |cards: &[&str]| -> Vec<Card> {
+            cards.iter().map(|card| card.parse().unwrap()).collect()
+        };
+
+        let mut gofish = GoFish::with_seed(0);
+        gofish.hands = [cards(hands[0]), cards(hands[1])];
+        gofish.pool = Deck::from_cards(&cards(pool));
+        gofish
+    }
+
+    #[test]
+    fn test_deal() {
+        let gofish = GoFish::with_seed(1);
+
+        assert_eq!(gofish.hands[0].len(), 7);
+        assert_eq!(gofish.hands[1].len(), 7);
+        assert_eq!(gofish.pool.remaining(), 38);
+        assert_eq!(gofish.num_players(), 2);
+    }
+
+    #[test]
+    fn test_ask() {
+        let mut gofish = gofish([&["7H", "2C"], &["7S", "7D", "KH"]], &["3C"]);
+
+        assert_eq!(
+            gofish.legal_actions(),
+            vec![Action(Rank::Two), Action(Rank::Seven)]
+        );
+
+        let ((hand, _, other, pool), reward, done) = gofish.step(Action(Rank::Seven));
+
+        assert_eq!(hand.len(), 4);
+        assert_eq!(other, 1);
+        assert_eq!(pool, 1);
+        assert_eq!(reward, 0);
+        assert!(!done);
+
+        // a successful ask keeps the turn
+        assert_eq!(*gofish.current_player(), 0);
+    }
+
+    #[test]
+    fn test_go_fish() {
+        let mut unlucky = gofish([&["7H"], &["KH"]], &["3C", "4D"]);
+
+        let ((hand, _, _, pool), reward, _) = unlucky.step(Action(Rank::Seven));
+
+        // player 1 is now asking, with their own hand in view
+        assert_eq!(*unlucky.current_player(), 1);
+        assert_eq!(hand, vec![Card::new(Suit::Hearts, Rank::King)]);
+        assert_eq!(pool, 1);
+        assert_eq!(reward, 0);
+        assert_eq!(unlucky.hands[0].len(), 2);
+
+        // fishing the asked rank keeps the turn
+        let mut lucky = gofish([&["7H"], &["KH"]], &["7C", "4D"]);
+        lucky.step(Action(Rank::Seven));
+        assert_eq!(*lucky.current_player(), 0);
+    }
+
+    #[test]
+    fn test_book() {
+        let mut gofish = gofish([&["9H", "9C", "9D", "2S"], &["9S", "QH"]], &["3C"]);
+
+        let ((hand, books, _, _), reward, done) = gofish.step(Action(Rank::Nine));
+
+        assert_eq!(reward, 1);
+        assert_eq!(books, [1, 0]);
+        assert_eq!(hand, vec![Card::new(Suit::Spades, Rank::Two)]);
+        assert_eq!(gofish.books[0], vec![Rank::Nine]);
+        assert!(!done);
+    }
+
+    #[test]
+    fn test_play_through() {
+        let mut gofish = GoFish::with_seed(5);
+
+        let mut rewards = 0;
+
+        for (_, _, reward, _) in gofish.clone().play(|game| game.legal_actions()[0]) {
+            rewards += reward as usize;
+        }
+
+        assert_eq!(rewards, 13);
+
+        while !gofish.is_terminal() {
+            gofish.step(gofish.legal_actions()[0]);
+        }
+
+        assert_eq!(gofish.books().iter().sum::<usize>(), 13);
+    }
+}
@@ -2,92 +2,244 @@
 
 use std::cmp::Ordering;
 
-use crate::game::Game;
+use crate::game::{ActionSpace, Game, ObservationSpace};
 
 use rand::prelude::*;
 
-use crate::card::standard::{self, Card, Rank, Suit};
+use crate::card::standard::{deck, validate_deck, AceOrder, Card, Deck, Rank};
 use crate::card::ConditionalOrd;
 
-impl ConditionalOrd for Suit {
-    // Leading card, optional trumps suit
-    type Info = ();
+const STARTING_BANK: u32 = 100;
 
-    fn compare(&self, _: &Self, _: &Self::Info) -> Ordering {
-        Ordering::Equal
-    }
-}
+// how many ranks apart the cards must be for a much higher or lower guess
+const MARGIN: u8 = 3;
 
-impl Rank {
-    fn value(&self) -> u8 {
-        match self {
-            Rank::Two => 2,
-            Rank::Three => 3,
-            Rank::Four => 4,
-            Rank::Five => 5,
-            Rank::Six => 6,
-            Rank::Seven => 7,
-            Rank::Eight => 8,
-            Rank::Nine => 9,
-            Rank::Ten => 10,
-            Rank::Jack => 11,
-            Rank::Queen => 12,
-            Rank::King => 13,
-            Rank::Ace => 14,
-        }
-    }
-}
-
-impl ConditionalOrd for Rank {
-    // No info needed for Whist
-    type Info = ();
+// the longest streak within one deck, a margin guess for every card after the first
+const DECK_STREAK: u8 = 2 * 51;
 
-    fn compare(&self, other: &Self, _: &Self::Info) -> Ordering {
-        self.value().cmp(&other.value())
-    }
+// how a guess is scored when the next card has the same rank
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TieRule {
+    Push,
+    Win,
+    Loss,
 }
 
-impl ConditionalOrd for Card {
-    // Leading card, optional trumps suit
-    type Info = ();
-
-    fn compare(&self, other: &Self, _: &Self::Info) -> Ordering {
-        self.rank.compare(&other.rank, &())
-    }
+enum Outcome {
+    Win,
+    // a correct much higher or lower guess
+    Bonus,
+    Push,
+    Loss,
 }
 
+#[derive(Clone)]
 pub struct HighLow {
-    deck: Vec<Card>,
+    deck: Deck,
     card: Card,
     score: u8,
+    bank: u32,
+    tie_rule: TieRule,
+    // reshuffled since the last reset, so the streak can outlast a deck
+    endless: bool,
+    // the card, score and bank before each guess, for undo
+    history: Vec<(Card, u8, u32)>,
     rng: SmallRng,
 }
 
 impl HighLow {
     pub fn new() -> Self {
-        let mut deck = standard::deck().to_vec();
+        HighLow::with_tie_rule(TieRule::Loss)
+    }
+
+    pub fn with_tie_rule(tie_rule: TieRule) -> Self {
+        let mut deck = Deck::new();
         let mut rng = SmallRng::from_entropy();
 
         deck.shuffle(&mut rng);
-        let card = deck.pop().unwrap();
+        let card = deck.draw().unwrap();
 
         Self {
             deck,
             card,
             score: 0,
+            bank: STARTING_BANK,
+            tie_rule,
+            endless: false,
+            history: Vec::new(),
             rng,
         }
     }
 
+    // resumes a game without shuffling, the first card of `deck` is drawn next,
+    // panics unless the cards are distinct standard cards
+    pub fn from_parts(deck: Vec<Card>, current: Card, score: u8) -> Self {
+        assert!(
+            deck.len() <= 51,
+            "at most 51 cards are left after the current card"
+        );
+        assert!(
+            deck.iter().all(|card| card.rank != Rank::Joker) && current.rank != Rank::Joker,
+            "HighLow is played without jokers"
+        );
+        assert!(
+            !deck.contains(&current),
+            "{} is both current and in the deck",
+            current
+        );
+
+        if let Err(error) = validate_deck(&deck) {
+            panic!("{}", error);
+        }
+
+        Self {
+            deck: Deck::from_cards(&deck),
+            card: current,
+            score,
+            ..HighLow::new()
+        }
+    }
+
+    // replaces the generator, so the following reset is reproducible
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    // shuffles every card but the current one back into the deck for an
+    // endless game, keeping the score and bank, undo stops at the reshuffle
+    pub fn reshuffle(&mut self) {
+        let cards: Vec<_> = deck()
+            .into_iter()
+            .filter(|card| *card != self.card)
+            .collect();
+
+        self.deck = Deck::from(cards);
+        self.deck.shuffle(&mut self.rng);
+        self.endless = true;
+        self.history.clear();
+    }
+
     pub fn score(&self) -> u8 {
         self.score
     }
+
+    pub fn bank(&self) -> u32 {
+        self.bank
+    }
+
+    // wagers `stake` points of the bank on the guess, returns (card, bank, done)
+    pub fn step_with_stake(&mut self, action: Action, stake: u8) -> (Card, u32, bool) {
+        if self.bank == 0 {
+            return (self.card, self.bank, true);
+        }
+
+        match self.guess(action) {
+            Some(Outcome::Win) => self.bank += stake as u32,
+            Some(Outcome::Bonus) => self.bank += 2 * stake as u32,
+            Some(Outcome::Push) => {}
+            Some(Outcome::Loss) => self.bank = self.bank.saturating_sub(stake as u32),
+            None => return (self.card, self.bank, true),
+        }
+
+        (self.card, self.bank, self.is_terminal())
+    }
+
+    // the chance the next card scores for the action, given the cards left
+    pub fn win_probability(&self, action: Action) -> f64 {
+        if self.deck.is_empty() {
+            return 0.0;
+        }
+
+        let wins = self
+            .deck
+            .iter()
+            .filter(|card| matches!(self.outcome(action, card), Outcome::Win | Outcome::Bonus))
+            .count();
+
+        wins as f64 / self.deck.remaining() as f64
+    }
+
+    // how the guess scores if `card` is the next card
+    fn outcome(&self, action: Action, card: &Card) -> Outcome {
+        let higher = card.rank.compare(&self.card.rank, &AceOrder::High);
+        // a joker is never a margin away from anything
+        let margin = card
+            .rank_distance(&self.card)
+            .is_some_and(|distance| distance >= MARGIN);
+
+        match (action, higher) {
+            (_, Ordering::Equal) => match self.tie_rule {
+                TieRule::Push => Outcome::Push,
+                TieRule::Win => Outcome::Win,
+                TieRule::Loss => Outcome::Loss,
+            },
+            (Action::Higher, Ordering::Greater) | (Action::Lower, Ordering::Less) => Outcome::Win,
+            (Action::MuchHigher, Ordering::Greater) | (Action::MuchLower, Ordering::Less)
+                if margin =>
+            {
+                Outcome::Bonus
+            }
+            _ => Outcome::Loss,
+        }
+    }
+
+    // draws the next card and updates the streak, returns None if the deck is empty
+    fn guess(&mut self, action: Action) -> Option<Outcome> {
+        let card = self.deck.draw()?;
+        let outcome = self.outcome(action, &card);
+
+        self.history.push((self.card, self.score, self.bank));
+
+        // an endless streak stops growing at u8::MAX
+        match outcome {
+            Outcome::Win => self.score = self.score.saturating_add(1),
+            Outcome::Bonus => self.score = self.score.saturating_add(2),
+            Outcome::Push => {}
+            Outcome::Loss => self.score = 0,
+        }
+
+        self.card = card;
+
+        Some(outcome)
+    }
+
+    // the whole game rather than the observation
+    pub fn full_state(&self) -> FullState {
+        FullState {
+            card: self.card,
+            deck: self.deck.iter().rev().copied().collect(),
+            score: self.score,
+            bank: self.bank,
+            endless: self.endless,
+        }
+    }
+}
+
+// everything about the game, the order of the deck included, for debugging and replays
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullState {
+    pub card: Card,
+    // the cards left in the order they will be drawn
+    pub deck: Vec<Card>,
+    pub score: u8,
+    pub bank: u32,
+    // reshuffled, so the streak is not bounded by one deck
+    pub endless: bool,
+}
+
+impl Default for HighLow {
+    fn default() -> Self {
+        HighLow::new()
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub enum Action {
     Higher,
     Lower,
+    // at least MARGIN ranks higher or lower, scoring double
+    MuchHigher,
+    MuchLower,
 }
 
 impl From<u32> for Action {
@@ -95,6 +247,8 @@ impl From<u32> for Action {
         match action {
             0 => Action::Higher,
             1 => Action::Lower,
+            2 => Action::MuchHigher,
+            3 => Action::MuchLower,
             _ => panic!("Invalid action"),
         }
     }
@@ -105,6 +259,8 @@ impl From<Action> for u32 {
         match action {
             Action::Higher => 0,
             Action::Lower => 1,
+            Action::MuchHigher => 2,
+            Action::MuchLower => 3,
         }
     }
 }
@@ -114,13 +270,19 @@ impl Game for HighLow {
     type Player = u8;
     type Reward = u8;
     type State = Card;
+    type Snapshot = FullState;
 
     fn current_player(&self) -> &Self::Player {
         &1
     }
 
     fn legal_actions(&self) -> Vec<Self::Action> {
-        vec![Action::Higher, Action::Lower]
+        vec![
+            Action::Higher,
+            Action::Lower,
+            Action::MuchHigher,
+            Action::MuchLower,
+        ]
     }
 
     fn observation(&self) -> Self::State {
@@ -128,37 +290,555 @@ impl Game for HighLow {
     }
 
     fn step(&mut self, action: Self::Action) -> (Self::State, Self::Reward, bool) {
-        let card = self.deck.pop();
-
-        if card.is_none() {
-            return (self.card, self.score, true);
+        // a bust bank ends the game as surely as an empty deck
+        if !self.is_terminal() {
+            self.guess(action);
         }
 
-        let card = card.unwrap();
-        let higher = card.compare(&self.card, &());
+        (self.card, self.score, self.is_terminal())
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.reseed(seed);
+    }
+
+    fn reset(&mut self) {
+        self.deck = Deck::new();
+        self.deck.shuffle(&mut self.rng);
+        self.card = self.deck.draw().unwrap();
+        self.score = 0;
+        self.bank = STARTING_BANK;
+        self.endless = false;
+        self.history.clear();
+    }
+
+    fn action_space(&self) -> ActionSpace {
+        ActionSpace::Discrete(4)
+    }
+
+    fn action_from_u32(&self, action: u32) -> Option<Self::Action> {
+        (action < 4).then(|| Action::from(action))
+    }
+
+    // the undo history is not part of the snapshot
+    fn snapshot(&self) -> Self::Snapshot {
+        self.full_state()
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        self.deck = Deck::from_cards(&snapshot.deck);
+        self.card = snapshot.card;
+        self.score = snapshot.score;
+        self.bank = snapshot.bank;
+        self.endless = snapshot.endless;
+        self.history.clear();
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        // the current card as its u32 encoding
+        ObservationSpace::Discrete(52)
+    }
+
+    fn encode_observation(&self) -> Vec<f32> {
+        // one-hot of the current card
+        let mut encoded = vec![0.0; 52];
+        encoded[u32::from(self.card) as usize] = 1.0;
+        encoded
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.deck.is_empty() || self.bank == 0
+    }
 
-        if action == Action::Higher && higher == Ordering::Greater {
-            self.score += 1;
-        } else if action == Action::Lower && higher == Ordering::Less {
-            self.score += 1;
-        } else {
-            self.score = 0;
+    fn reward_bounds(&self) -> (f32, f32) {
+        // the reward is the streak, which after a reshuffle can carry on
+        // across decks until it saturates
+        match self.endless {
+            true => (0.0, u8::MAX as f32),
+            false => (0.0, DECK_STREAK as f32),
         }
+    }
 
+    fn undo(&mut self) -> bool {
+        let (card, score, bank) = match self.history.pop() {
+            Some(previous) => previous,
+            None => return false,
+        };
+
+        self.deck.put(self.card);
         self.card = card;
+        self.score = score;
+        self.bank = bank;
 
-        (self.card, self.score, self.deck.is_empty())
+        true
     }
 
-    fn reset(&mut self) {
-        self.deck = standard::deck().to_vec();
-        self.deck.shuffle(&mut self.rng);
-        self.card = self.deck.pop().unwrap();
-        self.score = 0;
+    fn num_players(&self) -> usize {
+        1
+    }
+
+    fn render_to(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        writeln!(w, "Current card: {}", self.card)?;
+        writeln!(w, "Score: {}", self.score)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::standard::{Rank, Suit};
+    use crate::game::RewardShaping;
+
+    #[test]
+    fn test_is_terminal() {
+        let mut highlow = HighLow::new();
+        assert!(!highlow.is_terminal());
+
+        for _ in 0..51 {
+            highlow.step(Action::Higher);
+        }
+
+        assert!(highlow.is_terminal());
+    }
+
+    #[test]
+    fn test_num_players() {
+        assert_eq!(HighLow::new().num_players(), 1);
+    }
+
+    #[test]
+    fn test_stake_streak() {
+        let mut highlow = HighLow::new();
+        highlow.card = Card::new(Suit::Hearts, Rank::Two);
+        highlow.deck = Deck::from(vec![
+            Card::new(Suit::Clubs, Rank::Five),
+            Card::new(Suit::Clubs, Rank::Four),
+            Card::new(Suit::Clubs, Rank::Three),
+        ]);
+
+        assert_eq!(highlow.step_with_stake(Action::Higher, 10).1, 110);
+        assert_eq!(highlow.step_with_stake(Action::Higher, 20).1, 130);
+        assert_eq!(
+            highlow.step_with_stake(Action::Higher, 5),
+            (highlow.card, 135, true)
+        );
+        assert_eq!(highlow.score(), 3);
+    }
+
+    #[test]
+    fn test_stake_bust() {
+        let mut highlow = HighLow::new();
+        highlow.card = Card::new(Suit::Hearts, Rank::Two);
+        highlow.deck = Deck::from(vec![
+            Card::new(Suit::Clubs, Rank::Five),
+            Card::new(Suit::Clubs, Rank::Four),
+        ]);
+
+        let (_, bank, done) = highlow.step_with_stake(Action::Lower, 150);
+        assert_eq!(bank, 0);
+        assert!(done);
+        assert!(highlow.is_terminal());
+
+        assert_eq!(highlow.step_with_stake(Action::Higher, 10).1, 0);
+        assert_eq!(highlow.deck.remaining(), 1);
+
+        // a plain step agrees the game is over and draws nothing
+        let current = highlow.card;
+        let (card, _, done) = highlow.step(Action::Higher);
+        assert!(done);
+        assert_eq!(card, current);
+        assert_eq!(highlow.deck.remaining(), 1);
     }
 
-    fn render(&self) {
-        println!("Current card: {}", self.card);
-        println!("Score: {}", self.score);
+    fn tie(tie_rule: TieRule) -> HighLow {
+        let mut highlow = HighLow::with_tie_rule(tie_rule);
+        highlow.card = Card::new(Suit::Hearts, Rank::Two);
+        highlow.deck = Deck::from(vec![
+            Card::new(Suit::Spades, Rank::Three),
+            Card::new(Suit::Diamonds, Rank::Three),
+            Card::new(Suit::Clubs, Rank::Three),
+        ]);
+
+        // build a streak of one before the tie
+        highlow.step(Action::Higher);
+        highlow
+    }
+
+    #[test]
+    fn test_tie_rules() {
+        let mut push = tie(TieRule::Push);
+        assert_eq!(push.step(Action::Higher).1, 1);
+        assert_eq!(push.step_with_stake(Action::Lower, 10).1, 100);
+
+        let mut win = tie(TieRule::Win);
+        assert_eq!(win.step(Action::Higher).1, 2);
+        assert_eq!(win.step_with_stake(Action::Lower, 10).1, 110);
+
+        let mut loss = tie(TieRule::Loss);
+        assert_eq!(loss.step(Action::Higher).1, 0);
+        assert_eq!(loss.step_with_stake(Action::Lower, 10).1, 90);
+    }
+
+    #[test]
+    fn test_clone() {
+        let mut highlow = HighLow::new();
+        highlow.step(Action::Higher);
+
+        let card = highlow.observation();
+        let score = highlow.score();
+
+        let mut clone = highlow.clone();
+        clone.step(Action::Lower);
+        clone.step(Action::Lower);
+
+        assert_eq!(highlow.observation(), card);
+        assert_eq!(highlow.score(), score);
+        assert_eq!(highlow.deck.remaining(), 50);
+        assert_eq!(clone.deck.remaining(), 48);
+    }
+
+    #[test]
+    fn test_reward_shaping() {
+        let mut highlow = HighLow::new();
+        highlow.card = Card::new(Suit::Hearts, Rank::Two);
+        highlow.deck = Deck::from(
+            [
+                Rank::Eight,
+                Rank::Seven,
+                Rank::Six,
+                Rank::Five,
+                Rank::Four,
+                Rank::Three,
+            ]
+            .map(|rank| Card::new(Suit::Clubs, rank))
+            .to_vec(),
+        );
+
+        // a bonus of 2 once the streak is longer than 5
+        let mut shaped = RewardShaping::new(highlow, |_: &HighLow, score: u8| {
+            if score > 5 {
+                score as f32 + 2.0
+            } else {
+                score as f32
+            }
+        });
+
+        let rewards: Vec<_> = (0..6).map(|_| shaped.step(Action::Higher).1).collect();
+
+        assert_eq!(rewards, vec![1.0, 2.0, 3.0, 4.0, 5.0, 8.0]);
+        assert_eq!(shaped.inner().score(), 6);
+
+        // the bounds are the inner game's, not the default
+        assert_eq!(shaped.reward_bounds(), shaped.inner().reward_bounds());
+        assert_eq!(shaped.reward_bounds(), (0.0, DECK_STREAK as f32));
+    }
+
+    #[test]
+    fn test_spaces() {
+        let highlow = HighLow::new();
+
+        assert_eq!(highlow.action_space(), ActionSpace::Discrete(4));
+        assert_eq!(highlow.observation_space(), ObservationSpace::Discrete(52));
+    }
+
+    #[test]
+    fn test_encode_observation() {
+        let mut highlow = HighLow::new();
+
+        for _ in 0..10 {
+            let encoded = highlow.encode_observation();
+            assert_eq!(encoded.len(), 52);
+            assert_eq!(encoded.iter().sum::<f32>(), 1.0);
+            assert_eq!(encoded[u32::from(highlow.observation()) as usize], 1.0);
+
+            highlow.step(Action::Higher);
+        }
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut highlow = HighLow::new();
+        highlow.step(Action::Higher);
+
+        let score = highlow.score();
+        let remaining = highlow.deck.remaining();
+        let card = highlow.card;
+
+        let (next, _, _) = highlow.peek(Action::Lower);
+
+        assert_eq!(highlow.score(), score);
+        assert_eq!(highlow.deck.remaining(), remaining);
+        assert_eq!(highlow.card, card);
+
+        // the original draws the same card the copy did
+        assert_eq!(highlow.step(Action::Lower).0, next);
+    }
+
+    #[test]
+    fn test_render_to() {
+        let highlow = HighLow::new();
+
+        let mut output = Vec::new();
+        highlow.render_to(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains(&highlow.card.to_string()));
+        assert!(output.contains("Score: 0"));
+    }
+
+    #[test]
+    fn test_win_probability() {
+        let mut highlow = HighLow::with_tie_rule(TieRule::Push);
+        highlow.card = Card::new(Suit::Hearts, Rank::Seven);
+        highlow.deck = Deck::from_cards(
+            &[Rank::Two, Rank::Seven, Rank::King, Rank::Ace, Rank::Five]
+                .map(|rank| Card::new(Suit::Spades, rank)),
+        );
+
+        assert_eq!(highlow.win_probability(Action::Higher), 0.4);
+        assert_eq!(highlow.win_probability(Action::Lower), 0.4);
+
+        highlow.tie_rule = TieRule::Win;
+        assert_eq!(highlow.win_probability(Action::Higher), 0.6);
+
+        highlow.deck = Deck::from(Vec::new());
+        assert_eq!(highlow.win_probability(Action::Higher), 0.0);
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let deck = vec![
+            Card::new(Suit::Clubs, Rank::Nine),
+            Card::new(Suit::Spades, Rank::Four),
+        ];
+        let mut highlow = HighLow::from_parts(deck, Card::new(Suit::Hearts, Rank::Six), 4);
+
+        assert_eq!(highlow.score(), 4);
+        assert_eq!(highlow.observation(), Card::new(Suit::Hearts, Rank::Six));
+
+        assert_eq!(
+            highlow.step(Action::Higher),
+            (Card::new(Suit::Clubs, Rank::Nine), 5, false)
+        );
+        assert_eq!(
+            highlow.step(Action::Higher),
+            (Card::new(Suit::Spades, Rank::Four), 0, true)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "both current and in the deck")]
+    fn test_from_parts_overlap() {
+        let card = Card::new(Suit::Hearts, Rank::Six);
+        HighLow::from_parts(vec![card], card, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "without jokers")]
+    fn test_from_parts_joker() {
+        let deck = vec![
+            Card::new(Suit::Clubs, Rank::Nine),
+            Card::new(Suit::Spades, Rank::Joker),
+        ];
+        HighLow::from_parts(deck, Card::new(Suit::Hearts, Rank::Six), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "appears twice in the deck")]
+    fn test_from_parts_duplicate() {
+        let card = Card::new(Suit::Clubs, Rank::Nine);
+        HighLow::from_parts(vec![card, card], Card::new(Suit::Hearts, Rank::Six), 0);
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut highlow = HighLow::new();
+        assert!(!highlow.undo());
+
+        highlow.step(Action::Higher);
+        let before = (highlow.observation(), highlow.score(), highlow.bank());
+        let remaining = highlow.deck.remaining();
+
+        let (drawn, _, _) = highlow.step(Action::Lower);
+        highlow.step_with_stake(Action::Higher, 10);
+
+        assert!(highlow.undo());
+        assert!(highlow.undo());
+
+        assert_eq!(
+            (highlow.observation(), highlow.score(), highlow.bank()),
+            before
+        );
+        assert_eq!(highlow.deck.remaining(), remaining);
+
+        // the undone card is drawn again
+        assert_eq!(highlow.step(Action::Lower).0, drawn);
+    }
+
+    #[test]
+    fn test_stacked_tie() {
+        let mut highlow = HighLow::with_tie_rule(TieRule::Push);
+
+        highlow.deck.put(highlow.card);
+        highlow.deck.stack_top(&[
+            Card::new(Suit::Hearts, Rank::Seven),
+            Card::new(Suit::Spades, Rank::Seven),
+        ]);
+        highlow.card = highlow.deck.draw().unwrap();
+
+        // the tie is a push, so the bank neither wins nor loses the stake
+        let (card, bank, done) = highlow.step_with_stake(Action::Higher, 10);
+
+        assert_eq!(card, Card::new(Suit::Spades, Rank::Seven));
+        assert_eq!(bank, 100);
+        assert!(!done);
+        assert_eq!(highlow.deck.remaining(), 50);
+    }
+
+    #[test]
+    fn test_reward_bounds() {
+        let mut highlow = HighLow::with_tie_rule(TieRule::Win);
+        assert_eq!(highlow.reward_bounds(), (0.0, 102.0));
+
+        // every plain guess winning reaches half the upper bound on the last card
+        highlow.deck = Deck::from(vec![highlow.card; 51]);
+
+        let rewards: Vec<_> = highlow.play(|_| Action::Higher).map(|t| t.2).collect();
+        assert_eq!(*rewards.last().unwrap() as f32, 51.0);
+    }
+
+    #[test]
+    fn test_reseed() {
+        let mut a = HighLow::new();
+        let mut b = HighLow::new();
+
+        for highlow in [&mut a, &mut b] {
+            highlow.step(Action::Higher);
+            highlow.reseed(3);
+            highlow.reset();
+        }
+
+        assert_eq!(a.card, b.card);
+        assert_eq!(a.deck, b.deck);
+
+        assert_eq!(a.step(Action::Lower), b.step(Action::Lower));
+    }
+
+    #[test]
+    fn test_default() {
+        let highlow = HighLow::default();
+
+        assert_eq!(highlow.score(), 0);
+        assert_eq!(highlow.bank(), 100);
+        assert_eq!(highlow.deck.remaining(), 51);
+    }
+
+    #[test]
+    fn test_margin_guesses() {
+        let mut highlow = HighLow::new();
+        highlow.card = Card::new(Suit::Hearts, Rank::Four);
+        highlow.deck = Deck::from_cards(
+            &[Rank::Eight, Rank::Six, Rank::Seven, Rank::Ace]
+                .map(|rank| Card::new(Suit::Clubs, rank)),
+        );
+
+        // four ranks higher scores the bonus
+        assert_eq!(highlow.step(Action::MuchHigher).1, 2);
+        // only two ranks lower is a loss
+        assert_eq!(highlow.step_with_stake(Action::MuchLower, 10).1, 90);
+        assert_eq!(highlow.score(), 0);
+
+        // a plain guess still scores one
+        assert_eq!(highlow.step(Action::Higher).1, 1);
+        assert_eq!(highlow.step_with_stake(Action::MuchHigher, 10).1, 110);
+        assert_eq!(highlow.score(), 3);
+
+        assert_eq!(u32::from(Action::MuchLower), 3);
+        assert!(Action::from(2) == Action::MuchHigher);
+    }
+
+    #[test]
+    fn test_full_state() {
+        let mut highlow = HighLow::new();
+        let state = highlow.full_state();
+
+        assert_eq!(state.card, highlow.observation());
+        assert_eq!(state.deck.len(), 51);
+
+        // the deck is revealed in the order it is drawn
+        for card in state.deck.iter().take(5) {
+            assert_eq!(highlow.step(Action::Higher).0, *card);
+        }
+    }
+
+    #[test]
+    fn test_reshuffle() {
+        let mut highlow = HighLow::with_tie_rule(TieRule::Win);
+
+        while !highlow.is_terminal() {
+            highlow.step(Action::Higher);
+        }
+
+        let card = highlow.observation();
+        let score = highlow.score();
+
+        highlow.reshuffle();
+
+        assert_eq!(highlow.deck.remaining(), 51);
+        assert!(!highlow.deck.iter().any(|other| *other == card));
+        assert_eq!(highlow.score(), score);
+        assert!(!highlow.is_terminal());
+        assert!(!highlow.undo());
+
+        // the streak is no longer bounded by one deck
+        assert_eq!(highlow.reward_bounds(), (0.0, 255.0));
+
+        // a tie wins, so the same card again extends the streak
+        highlow.deck = Deck::from(vec![highlow.card]);
+        highlow.score = 254;
+        highlow.step(Action::Higher);
+        assert_eq!(highlow.score(), 255);
+
+        highlow.reset();
+        assert_eq!(highlow.reward_bounds(), (0.0, 102.0));
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let mut highlow = HighLow::new();
+        highlow.step(Action::Higher);
+
+        let snapshot = highlow.snapshot();
+        let observation = highlow.observation();
+        let score = highlow.score();
+
+        let (next, _, _) = highlow.step(Action::Lower);
+        highlow.step(Action::Lower);
+
+        highlow.restore(snapshot);
+
+        assert_eq!(highlow.observation(), observation);
+        assert_eq!(highlow.score(), score);
+        assert_eq!(highlow.deck.remaining(), 50);
+
+        // the restored deck draws the same cards again
+        assert_eq!(highlow.step(Action::Lower).0, next);
+
+        // a reshuffle is restored with the rest, and so is its absence
+        highlow.reshuffle();
+        let endless = highlow.snapshot();
+
+        highlow.reset();
+        highlow.restore(endless);
+        assert_eq!(highlow.reward_bounds(), (0.0, 255.0));
+
+        let snapshot = highlow.snapshot();
+        highlow.restore(HighLow::new().snapshot());
+        assert_eq!(highlow.reward_bounds(), (0.0, DECK_STREAK as f32));
+
+        highlow.restore(snapshot);
+        assert!(highlow.full_state().endless);
     }
 }
@@ -0,0 +1,223 @@
+// a simple 1 player game guessing the color of the next card
+
+use crate::game::{ActionSpace, Game, ObservationSpace};
+
+use rand::prelude::*;
+
+use crate::card::standard::{Card, Color, Deck};
+
+#[derive(Clone)]
+pub struct RedBlack {
+    deck: Deck,
+    card: Option<Card>,
+    score: u8,
+    rng: SmallRng,
+}
+
+impl RedBlack {
+    pub fn new() -> Self {
+        RedBlack::from_rng(SmallRng::from_entropy())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        RedBlack::from_rng(SmallRng::seed_from_u64(seed))
+    }
+
+    fn from_rng(mut rng: SmallRng) -> Self {
+        let mut deck = Deck::new();
+        deck.shuffle(&mut rng);
+
+        Self {
+            deck,
+            card: None,
+            score: 0,
+            rng,
+        }
+    }
+
+    pub fn score(&self) -> u8 {
+        self.score
+    }
+
+    // the whole game rather than the observation
+    pub fn full_state(&self) -> FullState {
+        FullState {
+            card: self.card,
+            deck: self.deck.iter().rev().copied().collect(),
+            score: self.score,
+        }
+    }
+}
+
+// everything about the game, the order of the deck included, for debugging and replays
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullState {
+    pub card: Option<Card>,
+    // the cards left in the order they will be drawn
+    pub deck: Vec<Card>,
+    pub score: u8,
+}
+
+impl Default for RedBlack {
+    fn default() -> Self {
+        RedBlack::new()
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum Action {
+    Red,
+    Black,
+}
+
+impl From<u32> for Action {
+    fn from(action: u32) -> Self {
+        match action {
+            0 => Action::Red,
+            1 => Action::Black,
+            _ => panic!("Invalid action"),
+        }
+    }
+}
+
+impl From<Action> for u32 {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::Red => 0,
+            Action::Black => 1,
+        }
+    }
+}
+
+impl Game for RedBlack {
+    type Action = Action;
+    type Player = u8;
+    type Reward = u8;
+    // the last revealed card, None before the first guess
+    type State = Option<Card>;
+    type Snapshot = FullState;
+
+    fn current_player(&self) -> &Self::Player {
+        &1
+    }
+
+    fn legal_actions(&self) -> Vec<Self::Action> {
+        vec![Action::Red, Action::Black]
+    }
+
+    fn observation(&self) -> Self::State {
+        self.card
+    }
+
+    fn step(&mut self, action: Self::Action) -> (Self::State, Self::Reward, bool) {
+        let card = match self.deck.draw() {
+            Some(card) => card,
+            None => return (self.card, 0, true),
+        };
+
+        let guess = match action {
+            Action::Red => Color::Red,
+            Action::Black => Color::Black,
+        };
+
+        let reward = (card.suit.color() == guess) as u8;
+
+        self.score += reward;
+        self.card = Some(card);
+
+        (self.card, reward, self.deck.is_empty())
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    fn reset(&mut self) {
+        self.deck = Deck::new();
+        self.deck.shuffle(&mut self.rng);
+        self.card = None;
+        self.score = 0;
+    }
+
+    fn action_space(&self) -> ActionSpace {
+        ActionSpace::Discrete(2)
+    }
+
+    fn action_from_u32(&self, action: u32) -> Option<Self::Action> {
+        (action < 2).then(|| Action::from(action))
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.full_state()
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        self.deck = Deck::from_cards(&snapshot.deck);
+        self.card = snapshot.card;
+        self.score = snapshot.score;
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        // the last card as its u32 encoding, 52 before the first guess
+        ObservationSpace::Discrete(53)
+    }
+
+    fn encode_observation(&self) -> Vec<f32> {
+        // one-hot of the last card, all zero before the first guess
+        let mut encoded = vec![0.0; 52];
+
+        if let Some(card) = self.card {
+            encoded[u32::from(card) as usize] = 1.0;
+        }
+
+        encoded
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.deck.is_empty()
+    }
+
+    fn num_players(&self) -> usize {
+        1
+    }
+
+    fn render_to(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        match self.card {
+            Some(card) => writeln!(w, "Last card: {}", card),
+            None => writeln!(w, "Last card: none"),
+        }?;
+        writeln!(w, "Score: {}", self.score)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_play_through() {
+        let mut redblack = RedBlack::with_seed(17);
+        let mut replay = RedBlack::with_seed(17);
+
+        let mut total = 0;
+
+        for i in 0..52 {
+            let (card, reward, done) = redblack.step(Action::Red);
+            assert_eq!(replay.step(Action::Black).0, card);
+
+            assert_eq!(reward == 1, card.unwrap().suit.is_red());
+            assert_eq!(done, i == 51);
+            total += reward;
+        }
+
+        // half the deck is red
+        assert_eq!(total, 26);
+        assert_eq!(redblack.score(), 26);
+        assert_eq!(replay.score(), 26);
+        assert!(redblack.is_terminal());
+
+        assert_eq!(redblack.step(Action::Red).1, 0);
+    }
+}
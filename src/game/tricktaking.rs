@@ -0,0 +1,486 @@
+// the trick-taking engine shared by Whist style games, for any number of players
+
+use std::ops::Index;
+
+use crate::card::max_conditional;
+use crate::card::standard::{Card, Deck, Pile, Suit};
+
+// the most cards a hand holds, the deck split between three players
+pub const MAX_HAND: usize = 17;
+
+// up to MAX_HAND cards held in fixed slots, so a slot index names the same card
+// for as long as it is held, which is what actions refer to
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Hand {
+    slots: [Option<Card>; MAX_HAND],
+}
+
+impl Hand {
+    pub fn new() -> Hand {
+        Hand::default()
+    }
+
+    // puts the card in the first empty slot and returns that slot
+    pub fn add(&mut self, card: Card) -> u8 {
+        let slot = self
+            .slots
+            .iter()
+            .position(|held| held.is_none())
+            .expect("a hand holds at most 17 cards");
+
+        self.slots[slot] = Some(card);
+        slot as u8
+    }
+
+    // takes the card out of the hand, returning the slot it was in
+    pub fn remove(&mut self, card: &Card) -> Option<u8> {
+        let slot = self.position(card)?;
+        self.slots[slot as usize] = None;
+        Some(slot)
+    }
+
+    // puts the card back into a specific empty slot
+    pub fn insert(&mut self, slot: u8, card: Card) {
+        let held = &mut self.slots[slot as usize];
+        assert!(held.is_none(), "slot {} is already holding a card", slot);
+
+        *held = Some(card);
+    }
+
+    // takes whatever is in the slot, leaving it empty
+    pub fn take(&mut self, slot: u8) -> Option<Card> {
+        self.slots[slot as usize].take()
+    }
+
+    pub fn position(&self, card: &Card) -> Option<u8> {
+        self.slots
+            .iter()
+            .position(|held| held.as_ref() == Some(card))
+            .map(|slot| slot as u8)
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.position(card).is_some()
+    }
+
+    // the held cards in slot order
+    pub fn iter(&self) -> impl Iterator<Item = &Card> {
+        self.slots.iter().flatten()
+    }
+
+    // the occupied slots and their cards
+    pub fn iter_slots(&self) -> impl Iterator<Item = (u8, &Card)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, held)| held.as_ref().map(|card| (slot as u8, card)))
+    }
+
+    pub fn slots(&self) -> &[Option<Card>; MAX_HAND] {
+        &self.slots
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.iter().all(|held| held.is_none())
+    }
+}
+
+impl Index<usize> for Hand {
+    type Output = Option<Card>;
+
+    fn index(&self, slot: usize) -> &Option<Card> {
+        &self.slots[slot]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Player {
+    pub(super) id: u32,
+    pub(super) hand: Hand,
+    pub(super) score: u8,
+}
+
+impl Player {
+    pub fn new(id: u32) -> Player {
+        Player {
+            id,
+            hand: Hand::new(),
+            score: 0,
+        }
+    }
+}
+
+impl From<Player> for u32 {
+    fn from(player: Player) -> u32 {
+        player.id
+    }
+}
+
+// a completed trick, each card with the id of the player who played it in play order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trick {
+    pub leader: u32,
+    pub winner: u32,
+    pub plays: Vec<(u32, Card)>,
+    // each player's hand just before their play, in play order
+    pub hands: Vec<Hand>,
+}
+
+impl Trick {
+    // the id of the first player who did not follow the leading suit while holding it
+    pub fn revoke(&self) -> Option<u32> {
+        let leading = self.plays.first()?.1.suit;
+
+        self.plays
+            .iter()
+            .zip(self.hands.iter())
+            .find(|((_, card), hand)| {
+                !card.is_of_suit(leading) && hand.iter().any(|held| held.is_of_suit(leading))
+            })
+            .map(|((id, _), _)| *id)
+    }
+}
+
+// the index of the card taking the trick: the highest trump, or failing
+// that the highest card of the leading suit
+pub fn trick_winner(cards: &[Card], leading: Suit, trumps: Option<Suit>) -> usize {
+    let best = max_conditional(cards, &(leading, trumps)).expect("a trick has cards");
+    cards.iter().position(|card| card == best).unwrap()
+}
+
+#[derive(Clone)]
+pub struct TrickTaking {
+    // seats in turn order, the player to act is always first
+    pub(super) players: Vec<Player>,
+    pub(super) trick: Vec<Card>,
+    pub(super) seen: Pile,
+    pub(super) trumps: Option<Suit>,
+    // each play's hand slot and, if it completed a trick, the seats moved to the winner
+    history: Vec<(u8, Option<usize>)>,
+    hand_size: usize,
+    // the hands of the players in the trick in progress, before each play
+    hands: Vec<Hand>,
+    pub(super) tricks: Vec<Trick>,
+}
+
+impl TrickTaking {
+    pub fn new(players_count: usize, trumps: Option<Suit>) -> TrickTaking {
+        assert!(
+            players_count >= 2,
+            "trick-taking needs at least two players"
+        );
+
+        TrickTaking {
+            players: (0..players_count as u32).map(Player::new).collect(),
+            trick: Vec::new(),
+            seen: Pile::new(),
+            trumps,
+            history: Vec::new(),
+            hand_size: (52 / players_count).min(MAX_HAND),
+            hands: Vec::new(),
+            tricks: Vec::new(),
+        }
+    }
+
+    pub fn players_count(&self) -> usize {
+        self.players.len()
+    }
+
+    // cards dealt to each hand, by default as many as a full deck allows
+    pub fn hand_size(&self) -> usize {
+        self.hand_size
+    }
+
+    // deals fewer cards to each hand, e.g. 7 for Knockout Whist
+    pub fn set_hand_size(&mut self, hand_size: usize) {
+        let most = (52 / self.players_count()).min(MAX_HAND);
+
+        assert!(
+            (1..=most).contains(&hand_size),
+            "each player is dealt between 1 and {} cards",
+            most
+        );
+
+        self.hand_size = hand_size;
+    }
+
+    // starts a new hand, dealing an equal number of cards to each player, at most
+    // hand_size, from the top of the deck, any left over stay in the deck
+    pub fn deal(&mut self, deck: &mut Deck) {
+        for player in self.players.iter_mut() {
+            player.hand = Hand::new();
+            player.score = 0;
+        }
+
+        self.trick.clear();
+        self.seen = Pile::new();
+        self.history.clear();
+        self.hands.clear();
+        self.tricks.clear();
+
+        let hand_size = (deck.remaining() / self.players_count()).min(self.hand_size);
+
+        for _ in 0..hand_size {
+            for player in self.players.iter_mut() {
+                player.hand.add(deck.draw().unwrap());
+            }
+        }
+    }
+
+    // rotates the seats, keeping their order, so the player with `id` acts next
+    pub fn set_leader(&mut self, id: u32) {
+        let seat = self
+            .players
+            .iter()
+            .position(|player| player.id == id)
+            .expect("no player with that id");
+
+        self.players.rotate_left(seat);
+    }
+
+    pub fn current_player(&self) -> &Player {
+        self.players.first().unwrap()
+    }
+
+    pub fn legal_actions(&self) -> Vec<u8> {
+        let player = self.current_player();
+        let held = player.hand.iter_slots();
+
+        let leading_suit = match self.trick.first() {
+            Some(leading) => leading.suit,
+            None => return held.map(|(slot, _)| slot).collect(),
+        };
+
+        // any card may be played when void in the leading suit
+        let follows = |card: &Card| card.is_of_suit(leading_suit);
+
+        if !player.hand.iter().any(follows) {
+            return held.map(|(slot, _)| slot).collect();
+        }
+
+        held.filter(|(_, card)| follows(card))
+            .map(|(slot, _)| slot)
+            .collect()
+    }
+
+    // plays the card in slot `action` for the current player,
+    // returns the id of the trick winner if this card completed the trick
+    pub fn play(&mut self, action: u8) -> Option<u32> {
+        let player = self.players.first_mut().unwrap();
+        self.hands.push(player.hand);
+        let card = player.hand.take(action).unwrap();
+
+        self.trick.push(card);
+        self.seen.push(card);
+
+        if self.trick.len() != self.players_count() {
+            self.players.rotate_left(1);
+            self.history.push((action, None));
+            return None;
+        }
+
+        let leading = self.trick.first().unwrap().suit;
+        let winner = trick_winner(&self.trick, leading, self.trumps);
+
+        // the leader now sits second, so the winner sits `winner + 1` seats along
+        let seats = self.players_count();
+        let plays: Vec<_> = self
+            .trick
+            .iter()
+            .enumerate()
+            .map(|(i, card)| (self.players[(i + 1) % seats].id, *card))
+            .collect();
+
+        self.players.rotate_left(winner + 1);
+        self.players[0].score += 1;
+        self.history.push((action, Some(winner + 1)));
+
+        self.tricks.push(Trick {
+            leader: plays[0].0,
+            winner: self.players[0].id,
+            plays,
+            hands: std::mem::take(&mut self.hands),
+        });
+
+        self.trick.clear();
+
+        Some(self.players[0].id)
+    }
+
+    // the id of the player whose card is winning the trick in progress
+    pub fn trick_leader(&self) -> Option<u32> {
+        let leading = self.trick.first()?.suit;
+        let position = trick_winner(&self.trick, leading, self.trumps);
+
+        // the player who led sits `trick.len()` seats from the end
+        let seats = self.players_count();
+        let seat = (seats - self.trick.len() + position) % seats;
+
+        Some(self.players[seat].id)
+    }
+
+    // takes back the last card played, returning false if nothing has been played
+    pub fn undo(&mut self) -> bool {
+        let (slot, rotated) = match self.history.pop() {
+            Some(play) => play,
+            None => return false,
+        };
+
+        match rotated {
+            Some(seats) => {
+                // the trick was completed, so take it back from the winner
+                self.players[0].score -= 1;
+                self.hands = self.tricks.pop().unwrap().hands;
+                self.players.rotate_right(seats);
+
+                let start = self.seen.len() - self.players_count();
+                self.trick = self.seen.as_slice()[start..].to_vec();
+            }
+            None => self.players.rotate_right(1),
+        }
+
+        let card = self.trick.pop().unwrap();
+        self.seen.pop();
+        self.hands.pop();
+        self.players[0].hand.insert(slot, card);
+
+        true
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.players.iter().all(|p| p.hand.is_empty())
+    }
+
+    // tricks taken by each player, indexed by player id
+    pub fn scores(&self) -> Vec<u8> {
+        let mut scores = vec![0; self.players_count()];
+
+        for player in self.players.iter() {
+            scores[player.id as usize] = player.score;
+        }
+
+        scores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::standard::Rank;
+
+    fn give(table: &mut TrickTaking, cards: &[Card]) {
+        for (player, card) in table.players.iter_mut().zip(cards) {
+            player.hand.add(*card);
+        }
+    }
+
+    #[test]
+    fn test_three_player_trick() {
+        let mut table = TrickTaking::new(3, Some(Suit::Spades));
+        give(
+            &mut table,
+            &[
+                Card::new(Suit::Hearts, Rank::Ace),
+                Card::new(Suit::Spades, Rank::Two),
+                Card::new(Suit::Hearts, Rank::King),
+            ],
+        );
+
+        assert_eq!(table.play(0), None);
+        assert_eq!(table.play(0), None);
+        assert_eq!(table.play(0), Some(1));
+
+        assert_eq!(table.current_player().id, 1);
+        assert_eq!(table.scores(), vec![0, 1, 0]);
+
+        let trick = &table.tricks[0];
+        assert_eq!((trick.leader, trick.winner), (0, 1));
+        assert_eq!(trick.plays[1], (1, Card::new(Suit::Spades, Rank::Two)));
+        assert!(table.is_finished());
+    }
+
+    #[test]
+    fn test_six_player_trick() {
+        let mut table = TrickTaking::new(6, None);
+        let mut deck = Deck::new();
+        table.deal(&mut deck);
+
+        assert_eq!(deck.remaining(), 4);
+
+        for _ in 0..6 {
+            let actions = table.legal_actions();
+            table.play(actions[0]);
+        }
+
+        assert_eq!(table.scores().iter().sum::<u8>(), 1);
+        assert_eq!(table.seen.len(), 6);
+        assert!(table.trick.is_empty());
+
+        let winner = table.current_player().id as usize;
+        assert_eq!(table.scores()[winner], 1);
+    }
+
+    #[test]
+    fn test_hand() {
+        let ace = Card::new(Suit::Hearts, Rank::Ace);
+        let two = Card::new(Suit::Clubs, Rank::Two);
+        let ten = Card::new(Suit::Spades, Rank::Ten);
+
+        let mut hand = Hand::new();
+        assert!(hand.is_empty());
+
+        assert_eq!(hand.add(ace), 0);
+        assert_eq!(hand.add(two), 1);
+        assert_eq!(hand.len(), 2);
+        assert!(hand.contains(&two));
+
+        // removing leaves a gap that keeps the other slots in place
+        assert_eq!(hand.remove(&ace), Some(0));
+        assert_eq!(hand.remove(&ace), None);
+        assert_eq!(hand[1], Some(two));
+        assert_eq!(hand.iter_slots().collect::<Vec<_>>(), vec![(1, &two)]);
+
+        // and the gap is filled first
+        assert_eq!(hand.add(ten), 0);
+        assert_eq!(hand.iter().collect::<Vec<_>>(), vec![&ten, &two]);
+
+        assert_eq!(hand.take(1), Some(two));
+        assert_eq!(hand.take(1), None);
+        assert_eq!(hand.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 17 cards")]
+    fn test_hand_full() {
+        let mut hand = Hand::new();
+
+        for card in Deck::new().deal(3).remove(1) {
+            hand.add(card);
+        }
+
+        hand.add(Card::new(Suit::Hearts, Rank::Joker));
+    }
+
+    #[test]
+    fn test_trick_winner() {
+        let cards = |cards: &[&str]| -> Vec<Card> {
+            cards.iter().map(|card| card.parse().unwrap()).collect()
+        };
+
+        // everyone follows, the highest of the suit wins
+        let trick = cards(&["5H", "KH", "2H", "AH"]);
+        assert_eq!(trick_winner(&trick, Suit::Hearts, Some(Suit::Spades)), 3);
+
+        // a trump ruffs the leading suit
+        let trick = cards(&["5H", "KH", "2S", "AH"]);
+        assert_eq!(trick_winner(&trick, Suit::Hearts, Some(Suit::Spades)), 2);
+
+        // a discard never wins, however high
+        let trick = cards(&["5H", "AC", "2H", "3H"]);
+        assert_eq!(trick_winner(&trick, Suit::Hearts, None), 0);
+    }
+}
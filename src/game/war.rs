@@ -0,0 +1,353 @@
+// the 2 player game of War, played from the point of view of player 0
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+use crate::game::{ActionSpace, Game, ObservationSpace};
+
+use rand::prelude::*;
+
+use crate::card::standard::{AceOrder, Card, Deck};
+use crate::card::ConditionalOrd;
+
+// cards placed face down by each player when a flip is tied
+const WAR_CARDS: usize = 3;
+
+// rounds before the game is called a draw, as won cards are never shuffled
+// some deals cycle forever
+const MAX_ROUNDS: usize = 2000;
+
+#[derive(Clone)]
+pub struct War {
+    // each player's pile, played from the front and won cards go to the back
+    hands: [VecDeque<Card>; 2],
+    rounds: usize,
+    rng: SmallRng,
+}
+
+impl War {
+    pub fn new() -> Self {
+        War::from_rng(SmallRng::from_entropy())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        War::from_rng(SmallRng::seed_from_u64(seed))
+    }
+
+    fn from_rng(rng: SmallRng) -> Self {
+        let mut war = War {
+            hands: [VecDeque::new(), VecDeque::new()],
+            rounds: 0,
+            rng,
+        };
+
+        war.deal();
+        war
+    }
+
+    fn deal(&mut self) {
+        let mut deck = Deck::new();
+        deck.shuffle(&mut self.rng);
+
+        for (hand, cards) in self.hands.iter_mut().zip(deck.deal(2)) {
+            *hand = cards.into();
+        }
+
+        self.rounds = 0;
+    }
+
+    pub fn cards_held(&self) -> [usize; 2] {
+        [self.hands[0].len(), self.hands[1].len()]
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    // the round limit was reached with both players still holding cards
+    pub fn is_draw(&self) -> bool {
+        self.rounds >= MAX_ROUNDS && self.hands.iter().all(|hand| !hand.is_empty())
+    }
+
+    // the whole game rather than the observation
+    pub fn full_state(&self) -> FullState {
+        FullState {
+            hands: self.hands.clone().map(Vec::from),
+            rounds: self.rounds,
+        }
+    }
+}
+
+// everything about the game, for debugging and replays
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullState {
+    // each player's pile from the next card played
+    pub hands: [Vec<Card>; 2],
+    pub rounds: usize,
+}
+
+impl Default for War {
+    fn default() -> Self {
+        War::new()
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum Action {
+    Flip,
+}
+
+impl From<u32> for Action {
+    fn from(action: u32) -> Self {
+        match action {
+            0 => Action::Flip,
+            _ => panic!("Invalid action"),
+        }
+    }
+}
+
+impl From<Action> for u32 {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::Flip => 0,
+        }
+    }
+}
+
+impl Game for War {
+    type Action = Action;
+    type Player = u8;
+    // cards taken from player 1, negative when player 0 loses cards
+    type Reward = i8;
+    // the number of cards each player holds
+    type State = [usize; 2];
+    type Snapshot = FullState;
+
+    fn current_player(&self) -> &Self::Player {
+        &0
+    }
+
+    fn legal_actions(&self) -> Vec<Self::Action> {
+        vec![Action::Flip]
+    }
+
+    fn observation(&self) -> Self::State {
+        self.cards_held()
+    }
+
+    fn step(&mut self, _: Self::Action) -> (Self::State, Self::Reward, bool) {
+        if self.is_terminal() {
+            return (self.observation(), 0, true);
+        }
+
+        // cards each player has put into the pot this round
+        let mut pot: [Vec<Card>; 2] = [Vec::new(), Vec::new()];
+
+        let winner = loop {
+            let (a, b) = match (self.hands[0].pop_front(), self.hands[1].pop_front()) {
+                (Some(a), Some(b)) => (a, b),
+                // a player who cannot flip during a war loses it
+                (a, b) => {
+                    let winner = if a.is_some() { 0 } else { 1 };
+                    pot[0].extend(a);
+                    pot[1].extend(b);
+                    break winner;
+                }
+            };
+
+            pot[0].push(a);
+            pot[1].push(b);
+
+            match a.rank.compare(&b.rank, &AceOrder::High) {
+                Ordering::Greater => break 0,
+                Ordering::Less => break 1,
+                Ordering::Equal => {
+                    // keep a card back for the next flip where possible
+                    for (hand, pot) in self.hands.iter_mut().zip(pot.iter_mut()) {
+                        let down = WAR_CARDS.min(hand.len().saturating_sub(1));
+                        pot.extend(hand.drain(..down));
+                    }
+                }
+            }
+        };
+
+        let loser = 1 - winner;
+        let won = pot[loser].len() as i8;
+
+        for pot in pot {
+            self.hands[winner].extend(pot);
+        }
+
+        let reward = if winner == 0 { won } else { -won };
+        self.rounds += 1;
+
+        (self.observation(), reward, self.is_terminal())
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    fn reset(&mut self) {
+        self.deal();
+    }
+
+    fn action_space(&self) -> ActionSpace {
+        ActionSpace::Discrete(1)
+    }
+
+    fn action_from_u32(&self, action: u32) -> Option<Self::Action> {
+        (action == 0).then_some(Action::Flip)
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.full_state()
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        self.hands = snapshot.hands.map(VecDeque::from);
+        self.rounds = snapshot.rounds;
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        // each player holds between 0 and 52 cards
+        ObservationSpace::MultiDiscrete(vec![53, 53])
+    }
+
+    fn encode_observation(&self) -> Vec<f32> {
+        // the fraction of the deck held by each player
+        self.cards_held()
+            .iter()
+            .map(|held| *held as f32 / 52.0)
+            .collect()
+    }
+
+    // a player is out of cards, or the round limit ends it as a draw
+    fn is_terminal(&self) -> bool {
+        self.hands.iter().any(|hand| hand.is_empty()) || self.rounds >= MAX_ROUNDS
+    }
+
+    fn reward_bounds(&self) -> (f32, f32) {
+        // at most every card but one changes hands in a round
+        (-51.0, 51.0)
+    }
+
+    fn num_players(&self) -> usize {
+        2
+    }
+
+    fn render_to(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        let [a, b] = self.cards_held();
+
+        writeln!(w, "Player 0: {} cards", a)?;
+        writeln!(w, "Player 1: {} cards", b)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::standard::{Rank, Suit};
+
+    fn war(hands: [&[Rank]; 2]) -> War {
+        let mut war = War::with_seed(0);
+        let suits = [Suit::Hearts, Suit::Spades];
+
+        for (hand, (ranks, suit)) in war.hands.iter_mut().zip(hands.iter().zip(suits)) {
+            *hand = ranks.iter().map(|rank| Card::new(suit, *rank)).collect();
+        }
+
+        war
+    }
+
+    #[test]
+    fn test_deal() {
+        let war = War::with_seed(1);
+        assert_eq!(war.cards_held(), [26, 26]);
+        assert_eq!(war.num_players(), 2);
+    }
+
+    #[test]
+    fn test_round() {
+        let mut war = war([&[Rank::King, Rank::Two], &[Rank::Five, Rank::Three]]);
+
+        let (held, reward, done) = war.step(Action::Flip);
+
+        assert_eq!(held, [3, 1]);
+        assert_eq!(reward, 1);
+        assert!(!done);
+        assert_eq!(war.hands[0].back().unwrap().rank, Rank::Five);
+    }
+
+    #[test]
+    fn test_war() {
+        let mut war = war([
+            &[Rank::Seven, Rank::Two, Rank::Two, Rank::Two, Rank::Ace],
+            &[
+                Rank::Seven,
+                Rank::Three,
+                Rank::Three,
+                Rank::Three,
+                Rank::King,
+                Rank::Four,
+            ],
+        ]);
+
+        let (held, reward, done) = war.step(Action::Flip);
+
+        // the ace beats the king after three cards each are put down
+        assert_eq!(held, [10, 1]);
+        assert_eq!(reward, 5);
+        assert!(!done);
+    }
+
+    #[test]
+    fn test_game_end() {
+        let mut war = war([&[Rank::Five], &[Rank::King]]);
+
+        let (held, reward, done) = war.step(Action::Flip);
+
+        assert_eq!(held, [0, 2]);
+        assert_eq!(reward, -1);
+        assert!(done);
+        assert!(war.is_terminal());
+
+        assert_eq!(war.step(Action::Flip), ([0, 2], 0, true));
+        assert!(!war.is_draw());
+    }
+
+    #[test]
+    fn test_round_limit() {
+        // from the second round the same position comes round every four
+        let mut war = war([&[Rank::Two, Rank::Four], &[Rank::Five, Rank::Three]]);
+        war.step(Action::Flip);
+        war.step(Action::Flip);
+
+        let cycle = war.full_state().hands;
+
+        for _ in 0..4 {
+            war.step(Action::Flip);
+        }
+
+        assert_eq!(war.full_state().hands, cycle);
+
+        let mut steps = 6;
+
+        while !war.is_terminal() {
+            war.step(Action::Flip);
+            steps += 1;
+        }
+
+        assert_eq!(steps, MAX_ROUNDS);
+        assert_eq!(war.rounds(), MAX_ROUNDS);
+        assert!(war.is_draw());
+        assert_eq!(war.cards_held().iter().sum::<usize>(), 4);
+        assert_eq!(war.step(Action::Flip).1, 0);
+
+        war.reset();
+        assert_eq!(war.rounds(), 0);
+        assert!(!war.is_terminal());
+    }
+}
@@ -1,116 +1,238 @@
-use rand::prelude::*;
-use std::cmp::Ordering;
+//! Four player Whist, or three player cutthroat Whist.
+//!
+//! ```
+//! use cards::game::whist::Whist;
+//! use cards::game::Game;
+//!
+//! let mut whist = Whist::new();
+//! assert_eq!(whist.legal_actions().len(), 13);
+//!
+//! whist.try_step(whist.legal_actions()[0]).unwrap();
+//! assert_eq!(whist.history().len(), 0);
+//!
+//! // throws in the hand and deals another
+//! whist.deal();
+//! assert_eq!(whist.legal_actions().len(), 13);
+//! ```
 
-use crate::card::standard;
-use crate::card::standard::{Card, Rank, Suit};
-use crate::card::ConditionalOrd;
-use crate::game::Game;
+use rand::prelude::*;
 
-impl ConditionalOrd for Suit {
-    // Leading card, optional trumps suit
-    type Info = (Suit, Option<Suit>);
+use crate::card::standard::{self, Card, Deck, Suit};
+use crate::error::CardsError;
+use crate::game::tricktaking::TrickTaking;
+use crate::game::{ActionSpace, Game, IllegalAction, ObservationSpace};
 
-    fn compare(&self, other: &Self, info: &Self::Info) -> Ordering {
-        let (leading, trumps) = info;
-        let trumps = trumps.unwrap_or(*leading);
+pub use crate::game::tricktaking::{trick_winner, Hand, Player, Trick};
 
-        if self == other {
-            return Ordering::Equal;
-        }
+// why a card could not be played by Whist::play_card
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayError {
+    // the current player does not hold the card
+    NotHeld(Card),
+    // the card is held but playing it breaks the rules, e.g. a revoke
+    Illegal(IllegalAction),
+}
 
-        if other == &trumps {
-            return Ordering::Less;
+impl std::fmt::Display for PlayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlayError::NotHeld(card) => write!(f, "{} is not in the current hand", card),
+            PlayError::Illegal(illegal) => write!(f, "{}", illegal),
         }
-
-        Ordering::Greater
     }
 }
 
-impl Rank {
-    fn value(&self) -> u8 {
-        match self {
-            Rank::Two => 2,
-            Rank::Three => 3,
-            Rank::Four => 4,
-            Rank::Five => 5,
-            Rank::Six => 6,
-            Rank::Seven => 7,
-            Rank::Eight => 8,
-            Rank::Nine => 9,
-            Rank::Ten => 10,
-            Rank::Jack => 11,
-            Rank::Queen => 12,
-            Rank::King => 13,
-            Rank::Ace => 14,
+impl std::error::Error for PlayError {}
+
+impl From<PlayError> for CardsError {
+    fn from(error: PlayError) -> Self {
+        match error {
+            PlayError::NotHeld(card) => CardsError::CardNotHeld(card),
+            PlayError::Illegal(illegal) => illegal.into(),
         }
     }
 }
 
-impl ConditionalOrd for Rank {
-    // No info needed for Whist
-    type Info = ();
+// a borrowed view of the current player's observation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WhistObservation<'a> {
+    pub hand: &'a Hand,
+    pub seen: &'a [Card],
+    pub trumps: Option<Suit>,
+    pub trick: &'a [Card],
+}
 
-    fn compare(&self, other: &Self, _: &Self::Info) -> Ordering {
-        self.value().cmp(&other.value())
-    }
+// what a player is trying to do with tricks
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ScoringMode {
+    Normal,
+    // take as few tricks as possible
+    Misere,
+}
+
+// the table, kitty, dealer and bids, restoring a game without its rng or settings
+#[derive(Clone)]
+pub struct Snapshot {
+    table: TrickTaking,
+    kitty: Vec<Card>,
+    dealer: usize,
+    bids: Vec<Option<u8>>,
 }
 
-impl ConditionalOrd for Card {
-    // Leading card, optional trumps suit
-    type Info = (Suit, Option<Suit>);
+// how a player's bid is rewarded when the hand is over
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BidScoring {
+    // +1 for taking exactly the bid, -1 otherwise
+    Exact,
+    // as in Bid Whist, the bid for making it plus `overtrick` for each
+    // trick over, and `undertrick` lost for each trick short
+    BidWhist { overtrick: i8, undertrick: i8 },
+}
+
+impl BidScoring {
+    // saturates at the limits of an i8, which the builder keeps games clear of
+    pub fn reward(&self, bid: u8, taken: u8) -> i8 {
+        self.wide_reward(bid, taken)
+            .clamp(i8::MIN as i32, i8::MAX as i32) as i8
+    }
+
+    fn wide_reward(&self, bid: u8, taken: u8) -> i32 {
+        let (bid, taken) = (bid as i32, taken as i32);
 
-    fn compare(&self, other: &Self, info: &Self::Info) -> Ordering {
-        match self.suit.compare(&other.suit, info) {
-            Ordering::Equal => self.rank.compare(&other.rank, &()),
-            other => other,
+        match *self {
+            BidScoring::Exact if taken == bid => 1,
+            BidScoring::Exact => -1,
+            BidScoring::BidWhist { overtrick, .. } if taken >= bid => {
+                bid + overtrick as i32 * (taken - bid)
+            }
+            BidScoring::BidWhist { undertrick, .. } => -(undertrick as i32) * (bid - taken),
         }
     }
+
+    // the smallest and largest reward for a bid in a hand of `hand_size` tricks
+    fn bounds(&self, hand_size: u8) -> (i32, i32) {
+        (0..=hand_size)
+            .flat_map(|bid| (0..=hand_size).map(move |taken| self.wide_reward(bid, taken)))
+            .fold((0, 0), |(low, high), reward| {
+                (low.min(reward), high.max(reward))
+            })
+    }
+}
+
+#[derive(Clone)]
+pub struct Whist {
+    table: TrickTaking,
+    // cards left over after dealing, set aside face down, a draw pile when
+    // the hands are smaller than a full deal
+    kitty: Vec<Card>,
+    // deals the first card to, and is led by, the player after the dealer
+    dealer: usize,
+    bids: Vec<Option<u8>>,
+    // the trumps the builder was given, None to draw them again at each reset
+    fixed_trumps: Option<Option<Suit>>,
+    partnerships: bool,
+    scoring: ScoringMode,
+    bid_scoring: BidScoring,
+    rng: SmallRng,
 }
 
-#[derive(Debug)]
-pub struct Player {
-    id: u32,
-    hand: [Option<Card>; 13],
-    score: u8,
+// chainable configuration for a Whist game, unset options are chosen as in Whist::new
+#[derive(Debug, Clone)]
+pub struct WhistBuilder {
+    seed: Option<u64>,
+    // Some(None) plays without trumps, None picks them at random
+    trumps: Option<Option<Suit>>,
+    scoring: ScoringMode,
+    bid_scoring: BidScoring,
+    partnerships: bool,
+    players: usize,
+    hand_size: Option<usize>,
 }
 
-impl Player {
-    pub fn new(id: u32) -> Player {
-        Player {
-            id: id,
-            hand: [None; 13],
-            score: 0,
+impl Default for WhistBuilder {
+    fn default() -> Self {
+        WhistBuilder {
+            seed: None,
+            trumps: None,
+            scoring: ScoringMode::Normal,
+            bid_scoring: BidScoring::Exact,
+            partnerships: false,
+            players: 4,
+            hand_size: None,
         }
     }
 }
 
-impl From<Player> for u32 {
-    fn from(player: Player) -> u32 {
-        player.id as u32
+impl WhistBuilder {
+    pub fn new() -> WhistBuilder {
+        WhistBuilder::default()
     }
-}
 
-pub struct Whist {
-    players: [Player; 4],
-    trick: Vec<Card>,
-    seen: Vec<Card>,
-    trumps: Option<Suit>,
-    deck: [Card; 52],
-    rng: ThreadRng,
-}
+    pub fn seed(mut self, seed: u64) -> WhistBuilder {
+        self.seed = Some(seed);
+        self
+    }
 
-impl Whist {
-    pub fn new() -> Whist {
-        let mut rng = rand::thread_rng();
-        let deck = standard::deck();
-        let mut players = [
-            Player::new(0),
-            Player::new(1),
-            Player::new(2),
-            Player::new(3),
-        ];
+    pub fn trumps(mut self, trumps: Option<Suit>) -> WhistBuilder {
+        self.trumps = Some(trumps);
+        self
+    }
+
+    pub fn scoring(mut self, scoring: ScoringMode) -> WhistBuilder {
+        self.scoring = scoring;
+        self
+    }
 
-        let mut suits = vec![
+    pub fn bid_scoring(mut self, bid_scoring: BidScoring) -> WhistBuilder {
+        self.bid_scoring = bid_scoring;
+        self
+    }
+
+    // players 0 & 2 play against 1 & 3 and tricks are credited to the team,
+    // only with four players
+    pub fn partnerships(mut self, partnerships: bool) -> WhistBuilder {
+        self.partnerships = partnerships;
+        self
+    }
+
+    // 4 players, or 3 for cutthroat Whist where each hand is 17 cards
+    // and the one card left over goes to the kitty
+    pub fn players(mut self, players: usize) -> WhistBuilder {
+        assert!(
+            players == 3 || players == 4,
+            "Whist is played by three or four players"
+        );
+
+        self.players = players;
+        self
+    }
+
+    // cards dealt to each player, by default as many as the deck allows,
+    // the rest are left in the kitty as a draw pile
+    pub fn hand_size(mut self, hand_size: usize) -> WhistBuilder {
+        self.hand_size = Some(hand_size);
+        self
+    }
+
+    pub fn build(self) -> Whist {
+        let rng = match self.seed {
+            Some(seed) => SmallRng::seed_from_u64(seed),
+            None => SmallRng::from_entropy(),
+        };
+
+        let mut whist = self.build_with_rng(rng);
+        whist.deal();
+        whist
+    }
+
+    fn build_with_rng(self, mut rng: SmallRng) -> Whist {
+        assert!(
+            !self.partnerships || self.players == 4,
+            "partnerships need four players"
+        );
+
+        // trumps are always drawn so a seed deals the same cards either way
+        let mut suits = [
             Some(Suit::Hearts),
             Some(Suit::Clubs),
             Some(Suit::Diamonds),
@@ -119,187 +241,1732 @@ impl Whist {
         ];
         suits.shuffle(&mut rng);
 
+        let trumps = self.trumps.unwrap_or(suits[0]);
+
+        let mut table = TrickTaking::new(self.players, trumps);
+
+        if let Some(hand_size) = self.hand_size {
+            table.set_hand_size(hand_size);
+        }
+
+        // a step's reward is a trick and a bid, which must fit in an i8
+        let (low, high) = self.bid_scoring.bounds(table.hand_size() as u8);
+        assert!(
+            low > i8::MIN as i32 && high < i8::MAX as i32,
+            "bid rewards from {} to {} do not fit in an i8",
+            low,
+            high
+        );
+
         Whist {
-            players,
-            trick: Vec::new(),
-            seen: Vec::new(),
-            trumps: suits[0],
-            deck,
+            table,
+            kitty: Vec::new(),
+            // the last seat deals so player 0 leads
+            dealer: self.players - 1,
+            bids: vec![None; self.players],
+            fixed_trumps: self.trumps,
+            partnerships: self.partnerships,
+            scoring: self.scoring,
+            bid_scoring: self.bid_scoring,
             rng,
         }
     }
+}
+
+impl Whist {
+    pub fn new() -> Whist {
+        WhistBuilder::new().build()
+    }
+
+    pub fn builder() -> WhistBuilder {
+        WhistBuilder::new()
+    }
+
+    pub fn with_seed(seed: u64) -> Whist {
+        Whist::builder().seed(seed).build()
+    }
+
+    // 4 players, or 3 for cutthroat Whist, see WhistBuilder::players
+    pub fn with_players(players: usize) -> Whist {
+        Whist::builder().players(players).build()
+    }
+
+    // skips the random trump selection, None plays without trumps
+    pub fn with_trumps(trumps: Option<Suit>) -> Whist {
+        Whist::builder().trumps(trumps).build()
+    }
+
+    // see WhistBuilder::partnerships
+    pub fn with_partnerships() -> Whist {
+        Whist::builder().partnerships(true).build()
+    }
+
+    pub fn with_scoring(scoring: ScoringMode) -> Whist {
+        Whist::builder().scoring(scoring).build()
+    }
+
+    // replaces the generator, so the following reset and deal are reproducible
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    pub fn partnerships(&self) -> bool {
+        self.partnerships
+    }
+
+    pub fn scoring(&self) -> ScoringMode {
+        self.scoring
+    }
+
+    pub fn bid_scoring(&self) -> BidScoring {
+        self.bid_scoring
+    }
+
+    // a builder with every setting this game was built with, bar the seed
+    fn settings(&self) -> WhistBuilder {
+        WhistBuilder {
+            seed: None,
+            trumps: self.fixed_trumps,
+            scoring: self.scoring,
+            bid_scoring: self.bid_scoring,
+            partnerships: self.partnerships,
+            players: self.num_players(),
+            hand_size: Some(self.table.hand_size()),
+        }
+    }
+
+    pub fn trumps(&self) -> Option<Suit> {
+        self.table.trumps
+    }
+
+    // shuffles and deals a new hand, clearing the last one's tricks and bids
+    pub fn deal(&mut self) {
+        self.bids = vec![None; self.num_players()];
+
+        let mut deck = Deck::new();
+        deck.shuffle(&mut self.rng);
+
+        let leader = (self.dealer + 1) % self.num_players();
+        self.table.set_leader(leader as u32);
+
+        self.table.deal(&mut deck);
+        self.kitty = deck.iter().copied().collect();
+    }
+
+    pub fn dealer(&self) -> usize {
+        self.dealer
+    }
+
+    // takes effect at the next deal
+    pub fn set_dealer(&mut self, dealer: usize) {
+        assert!(dealer < self.num_players(), "no player {} to deal", dealer);
+        self.dealer = dealer;
+    }
+
+    pub fn kitty(&self) -> &[Card] {
+        &self.kitty
+    }
+
+    // the same as observation but without cloning, for tight self-play loops
+    pub fn observation_ref(&self) -> WhistObservation<'_> {
+        WhistObservation {
+            hand: &self.current_player().hand,
+            seen: self.table.seen.as_slice(),
+            trumps: self.table.trumps,
+            trick: &self.table.trick,
+        }
+    }
+
+    // every player's view, indexed by player id, each holding only their own hand
+    pub fn all_observations(&self) -> Vec<WhistObservation<'_>> {
+        let mut players: Vec<_> = self.table.players.iter().collect();
+        players.sort_by_key(|player| player.id);
+
+        players
+            .into_iter()
+            .map(|player| WhistObservation {
+                hand: &player.hand,
+                seen: self.table.seen.as_slice(),
+                trumps: self.table.trumps,
+                trick: &self.table.trick,
+            })
+            .collect()
+    }
+
+    // cards the current player has not seen: still in other players' hands
+    // or not dealt, but never played and not in their own hand
+    pub fn unseen(&self) -> Vec<Card> {
+        let hand = &self.current_player().hand;
+
+        standard::deck()
+            .into_iter()
+            .filter(|card| !self.table.seen.contains(card) && !hand.contains(card))
+            .collect()
+    }
+
+    pub fn place_bid(&mut self, player: usize, bid: u8) {
+        assert!(player < self.num_players(), "no player {} to bid", player);
+        assert!(
+            self.table.seen.is_empty(),
+            "bids are placed before the first trick"
+        );
+        assert!(
+            bid as usize <= self.table.hand_size(),
+            "cannot bid more tricks than there are in a hand"
+        );
+
+        self.bids[player] = Some(bid);
+    }
+
+    pub fn bids_met(&self) -> Vec<bool> {
+        self.tricks_won()
+            .into_iter()
+            .zip(self.bids.iter())
+            .map(|(score, bid)| *bid == Some(score))
+            .collect()
+    }
+
+    // completed tricks taken by each player, indexed by player id,
+    // whatever the scoring mode
+    pub fn tricks_won(&self) -> Vec<u8> {
+        self.table.scores()
+    }
+
+    // tricks taken by each player, indexed by player id,
+    // in misere the completed tricks each player avoided taking
+    pub fn scores(&self) -> Vec<u8> {
+        let taken = self.tricks_won();
+
+        match self.scoring {
+            ScoringMode::Normal => taken,
+            ScoringMode::Misere => {
+                let completed = (self.table.seen.len() / self.num_players()) as u8;
+                taken.into_iter().map(|taken| completed - taken).collect()
+            }
+        }
+    }
+
+    // tricks taken by each partnership, players 0 & 2 then players 1 & 3,
+    // None unless playing partnerships
+    pub fn team_scores(&self) -> Option<[u8; 2]> {
+        if !self.partnerships {
+            return None;
+        }
+
+        let scores = self.scores();
+
+        Some([scores[0] + scores[2], scores[1] + scores[3]])
+    }
+
+    // the id of the player with the most tricks, None if the top score is shared
+    pub fn winner(&self) -> Option<u32> {
+        let scores = self.scores();
+        let best = *scores.iter().max().unwrap();
+
+        let mut leaders = (0..scores.len()).filter(|i| scores[*i] == best);
+        let winner = leaders.next();
+
+        match leaders.next() {
+            Some(_) => None,
+            None => winner.map(|i| i as u32),
+        }
+    }
+
+    // every completed trick so far, in the order they were played
+    pub fn history(&self) -> &[Trick] {
+        &self.table.tricks
+    }
+
+    // each legal card paired with the id of the player who would be winning
+    // the trick after it is played, for a one-ply lookahead
+    pub fn possible_outcomes(&self) -> Vec<(u8, u32)> {
+        let seats = self.num_players();
+        let hand = &self.current_player().hand;
+
+        self.legal_actions()
+            .into_iter()
+            .map(|action| {
+                let mut trick = self.table.trick.clone();
+                trick.push(hand[action as usize].unwrap());
+
+                // the trick was led `trick.len() - 1` seats before the current player
+                let leading = trick[0].suit;
+                let winner = trick_winner(&trick, leading, self.table.trumps);
+                let seat = (seats + 1 - trick.len() + winner) % seats;
+
+                (action, self.table.players[seat].id)
+            })
+            .collect()
+    }
+
+    // the suits each player has shown out of by not following the leading
+    // suit, indexed by player id then suit
+    pub fn known_voids(&self) -> Vec<[bool; 4]> {
+        let mut voids = vec![[false; 4]; self.num_players()];
+
+        // the trick in progress was led `trick.len()` seats from the end
+        let seats = self.num_players();
+        let trick: Vec<_> = self
+            .table
+            .trick
+            .iter()
+            .enumerate()
+            .map(|(i, card)| {
+                let seat = (seats - self.table.trick.len() + i) % seats;
+                (self.table.players[seat].id, *card)
+            })
+            .collect();
+
+        let plays = self
+            .history()
+            .iter()
+            .map(|trick| trick.plays.as_slice())
+            .chain([trick.as_slice()]);
+
+        for plays in plays {
+            let leading = match plays.first() {
+                Some((_, card)) => card.suit,
+                None => continue,
+            };
+
+            for (id, card) in plays {
+                if card.suit != leading {
+                    voids[*id as usize][leading as usize] = true;
+                }
+            }
+        }
+
+        voids
+    }
+
+    // each completed trick with a revoke, as its index in history and the
+    // offending player, e.g. to validate games played with step_unchecked
+    pub fn revokes(&self) -> Vec<(usize, u32)> {
+        self.history()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, trick)| trick.revoke().map(|id| (i, id)))
+            .collect()
+    }
+
+    // what the last card played earned each player, indexed by player id: +1
+    // to the winner of the trick it completed, and their partner in
+    // partnerships, or -1 in misere, and if it ended the hand each player's
+    // bid reward
+    pub fn rewards(&self) -> Vec<i8> {
+        let mut rewards = vec![0; self.num_players()];
+
+        // no trick in progress means the last card completed one
+        let winner = match (self.table.trick.is_empty(), self.history().last()) {
+            (true, Some(trick)) => trick.winner,
+            _ => return rewards,
+        };
+
+        let reward = match self.scoring {
+            ScoringMode::Normal => 1,
+            ScoringMode::Misere => -1,
+        };
+
+        for (id, share) in rewards.iter_mut().enumerate() {
+            let id = id as u32;
+
+            if id == winner || (self.partnerships && id % 2 == winner % 2) {
+                *share = reward;
+            }
+        }
+
+        if self.is_terminal() {
+            let taken = self.tricks_won();
+
+            for (id, bid) in self.bids.iter().enumerate() {
+                if let Some(bid) = bid {
+                    rewards[id] += self.bid_scoring.reward(*bid, taken[id]);
+                }
+            }
+        }
+
+        rewards
+    }
+
+    // who would take the trick if it ended now, None before the first card
+    pub fn current_trick_leader(&self) -> Option<u32> {
+        self.table.trick_leader()
+    }
+
+    // plays the card in slot `action`, rejecting empty slots and revokes,
+    // where Game::step assumes the action is legal
+    pub fn try_step(
+        &mut self,
+        action: u8,
+    ) -> Result<(<Self as Game>::State, i8, bool), IllegalAction> {
+        let legal = self.table.legal_actions();
+
+        if !legal.is_empty() && !legal.contains(&action) {
+            return Err(IllegalAction(action as u32));
+        }
+
+        Ok(self.step_unchecked(action))
+    }
+
+    // plays a card by value rather than by hand slot, named so as not to
+    // shadow Game::play
+    pub fn play_card(
+        &mut self,
+        card: Card,
+    ) -> Result<(<Self as Game>::State, i8, bool), PlayError> {
+        let slot = self
+            .current_player()
+            .hand
+            .position(&card)
+            .ok_or(PlayError::NotHeld(card))?;
+
+        self.try_step(slot).map_err(PlayError::Illegal)
+    }
+
+    // as Game::step, the hot path for callers that only pick from legal_actions,
+    // the reward is only the acting player's share, see rewards for everyone's
+    pub fn step_unchecked(&mut self, action: u8) -> (<Self as Game>::State, i8, bool) {
+        // returns (observation, reward, done)
+        if self.table.legal_actions().is_empty() {
+            return (self.observation(), 0, true);
+        }
+
+        let id = self.current_player().id;
+
+        if self.table.play(action).is_none() {
+            return (self.observation(), 0, false);
+        }
+
+        // the acting player's own share of the trick they completed, and of
+        // the bids if it ended the hand
+        let reward = self.rewards()[id as usize];
+
+        (self.observation(), reward, self.is_terminal())
+    }
 
-    fn deal(&mut self) {
-        self.deck.shuffle(&mut self.rng);
+    // the whole game rather than one player's view of it
+    pub fn full_state(&self) -> FullState {
+        let mut players: Vec<_> = self.table.players.iter().collect();
+        players.sort_by_key(|player| player.id);
 
-        for (i, card) in self.deck.iter().enumerate() {
-            self.players[i % 4].hand[i / 4] = Some(*card);
+        FullState {
+            hands: players.iter().map(|player| player.hand).collect(),
+            kitty: self.kitty.clone(),
+            seen: self.table.seen.as_slice().to_vec(),
+            trick: self.table.trick.clone(),
+            trumps: self.table.trumps,
+            scores: self.scores(),
+            bids: self.bids.clone(),
+            dealer: self.dealer,
+            current_player: self.current_player().id,
         }
     }
 }
 
+// everything about the game, every hand included, for debugging and replays
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullState {
+    // indexed by player id
+    pub hands: Vec<Hand>,
+    pub kitty: Vec<Card>,
+    pub seen: Vec<Card>,
+    pub trick: Vec<Card>,
+    pub trumps: Option<Suit>,
+    pub scores: Vec<u8>,
+    pub bids: Vec<Option<u8>>,
+    pub dealer: usize,
+    pub current_player: u32,
+}
+
+impl Default for Whist {
+    fn default() -> Self {
+        Whist::new()
+    }
+}
+
 impl Game for Whist {
     type Action = u8;
     type Player = Player;
-    type Reward = u8;
-    type State = ([Option<Card>; 13], Vec<Card>, Option<Suit>, Vec<Card>);
+    type Reward = i8;
+    type State = (Hand, Vec<Card>, Option<Suit>, Vec<Card>);
+    type Snapshot = Snapshot;
 
     fn current_player(&self) -> &Player {
-        self.players.first().unwrap()
+        self.table.current_player()
     }
 
     fn observation(&self) -> Self::State {
         // observation = (hand, seen, trumps, trick)
+        let observation = self.observation_ref();
 
         (
-            self.current_player().hand,
-            self.seen.clone(),
-            self.trumps,
-            self.trick.clone(),
+            *observation.hand,
+            observation.seen.to_vec(),
+            observation.trumps,
+            observation.trick.to_vec(),
         )
     }
 
     fn legal_actions(&self) -> Vec<Self::Action> {
-        let player = self.current_player();
+        self.table.legal_actions()
+    }
 
-        let mut actions: Vec<_> = player
-            .hand
-            .iter()
-            .enumerate()
-            .filter(|(_, c)| c.is_some())
-            .map(|(i, _)| i as Self::Action)
-            .collect();
+    // skips validation like every Game::step, an illegal action panics or
+    // breaks the rules, see try_step to check it first. the reward is the
+    // acting player's alone, so a trick completed by one of the losers pays
+    // nothing here, and rewards has what the card earned every player
+    fn step(&mut self, action: Self::Action) -> (Self::State, Self::Reward, bool) {
+        self.step_unchecked(action)
+    }
 
-        if self.trick.is_empty() {
-            return actions;
-        }
+    fn seed(&mut self, seed: u64) {
+        self.reseed(seed);
+    }
 
-        let leading_suit = self.trick.first().expect("trick should not be empty").suit;
+    fn reset(&mut self) {
+        // carries on with the same generator so a reseed also fixes the next hand
+        *self = Whist {
+            dealer: self.dealer,
+            ..self.settings().build_with_rng(self.rng.clone())
+        };
 
-        let has_leading = player.hand.iter().any(|c| match c {
-            Some(c) => c.suit == leading_suit,
-            None => false,
-        });
+        self.deal();
+    }
 
-        if !has_leading {
-            return actions;
+    fn action_space(&self) -> ActionSpace {
+        // a slot in the hand
+        ActionSpace::Discrete(self.table.hand_size())
+    }
+
+    fn action_from_u32(&self, action: u32) -> Option<Self::Action> {
+        ((action as usize) < self.table.hand_size()).then_some(action as u8)
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        Snapshot {
+            table: self.table.clone(),
+            kitty: self.kitty.clone(),
+            dealer: self.dealer,
+            bids: self.bids.clone(),
         }
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        self.table = snapshot.table;
+        self.kitty = snapshot.kitty;
+        self.dealer = snapshot.dealer;
+        self.bids = snapshot.bids;
+    }
 
-        actions.retain(|i| player.hand[*i as usize].unwrap().suit == leading_suit);
+    fn observation_space(&self) -> ObservationSpace {
+        // each card is its u32 encoding with 52 for an empty slot: a slot per card
+        // in a hand, 52 seen slots, the trumps (4 for none) and a trick slot for
+        // every player but one
+        let mut space = vec![53; self.table.hand_size() + 52];
+        space.push(5);
+        space.extend(vec![53; self.num_players() - 1]);
 
-        actions
+        ObservationSpace::MultiDiscrete(space)
     }
 
-    fn step(&mut self, action: Self::Action) -> (Self::State, Self::Reward, bool) {
-        // returns (observation, reward, done)
-        let mut player = self.players.first_mut().unwrap();
-        let card = player.hand[action as usize].unwrap();
+    fn encode_observation(&self) -> Vec<f32> {
+        // [0, 52) hand mask, [52, 104) seen mask, [104, 108) trumps one-hot
+        // in suit order (all zero for no trumps), [108, 160) current trick mask
+        let mut encoded = vec![0.0; 160];
 
-        self.trick.push(card);
-        self.seen.push(card);
+        for card in self.current_player().hand.iter() {
+            encoded[u32::from(*card) as usize] = 1.0;
+        }
 
-        player.hand[action as usize] = None;
+        for card in self.table.seen.iter() {
+            encoded[52 + u32::from(*card) as usize] = 1.0;
+        }
 
-        if self.trick.len() != 4 {
-            self.players.rotate_left(1);
-            return (self.observation(), 0, false);
+        if let Some(trumps) = self.table.trumps {
+            encoded[104 + trumps as usize] = 1.0;
         }
 
-        let leading = self.trick.first().unwrap();
-        let mut trick = self.trick.clone();
-        trick.sort_by(|a, b| a.compare(b, &(leading.suit, self.trumps)));
+        for card in self.table.trick.iter() {
+            encoded[108 + u32::from(*card) as usize] = 1.0;
+        }
 
-        let winner = self
-            .trick
-            .iter()
-            .position(|c| c == trick.last().unwrap())
-            .unwrap();
+        encoded
+    }
 
-        self.players.rotate_left(winner + 1);
-        self.players[0].score += 1;
+    fn is_terminal(&self) -> bool {
+        self.table.is_finished()
+    }
 
-        self.trick.clear();
+    fn reward_bounds(&self) -> (f32, f32) {
+        // a trick is worth 1, or -1 in misere, and the last one can add the bid's reward
+        let (low, high) = self.bid_scoring.bounds(self.table.hand_size() as u8);
 
-        (self.observation(), 0, false)
+        match self.scoring {
+            ScoringMode::Normal => (low as f32, high as f32 + 1.0),
+            ScoringMode::Misere => (low as f32 - 1.0, high as f32),
+        }
     }
 
-    fn reset(&mut self) {
-        *self = Whist::new();
+    fn undo(&mut self) -> bool {
+        self.table.undo()
+    }
+
+    fn num_players(&self) -> usize {
+        self.table.players_count()
+    }
+
+    fn current_player_index(&self) -> usize {
+        self.current_player().id as usize
     }
 
-    fn render(&self) {
+    fn render_to(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
         let player = self.current_player();
 
-        println!("Player: {:?}", player);
-        println!("Trick: {:?}", self.trick);
-        println!("Trump: {:?}", self.trumps);
+        writeln!(w, "Player: {:?}", player)?;
+        writeln!(w, "Trick: {:?}", self.table.trick)?;
+        writeln!(w, "Trump: {:?}", self.table.trumps)?;
+
+        Ok(())
     }
 }
 
+#[cfg(test)]
 mod tests {
     use super::*;
+    use crate::card::standard::Rank;
 
     #[test]
     fn test_deal() {
-        let mut whist = Whist::new();
-        whist.deal();
+        let whist = Whist::new();
 
         let mut seen: Vec<Card> = Vec::new();
 
-        for player in whist.players.iter() {
+        for player in whist.table.players.iter() {
+            assert_eq!(player.hand.len(), 13);
+
             for card in player.hand.iter() {
-                assert!(card.is_some());
-                assert!(!seen.contains(&card.unwrap()));
-                seen.push(card.unwrap());
+                assert!(!seen.contains(card));
+                seen.push(*card);
             }
         }
     }
 
     #[test]
-    fn test_legal_actions() {
-        let mut whist = Whist::new();
+    fn test_deal_again() {
+        let mut whist = Whist::with_seed(29);
+        whist.place_bid(0, 4);
+
+        for _ in 0..6 {
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        }
+
+        // a second deal starts a fresh hand rather than adding to the first
         whist.deal();
 
-        let player = whist.current_player();
+        assert!(whist.table.players.iter().all(|p| p.hand.len() == 13));
+        assert!(whist.table.seen.is_empty() && whist.table.trick.is_empty());
+        assert!(whist.history().is_empty());
+        assert_eq!(whist.tricks_won(), [0; 4]);
+        assert_eq!(whist.full_state().bids, [None; 4]);
+        assert!(!whist.undo());
+    }
 
-        for card in player.hand.iter() {
-            if let Some(card) = card {
-                println!("{}", card.suit);
-            } else {
-                println!("None");
-            }
+    #[test]
+    fn test_with_seed() {
+        let a = Whist::with_seed(42);
+        let b = Whist::with_seed(42);
+
+        assert_eq!(a.table.trumps, b.table.trumps);
+
+        for (pa, pb) in a.table.players.iter().zip(b.table.players.iter()) {
+            assert_eq!(pa.hand, pb.hand);
         }
+    }
+
+    #[test]
+    fn test_legal_actions() {
+        let mut whist = Whist::new();
 
         let actions = whist.legal_actions();
         assert_eq!(actions.len(), 13);
 
-        println!("actions: {:?}", actions);
-
-        whist.trick.push(Card {
+        whist.table.trick.push(Card {
             suit: Suit::Hearts,
             rank: Rank::Ace,
         });
 
-        let actions = whist.legal_actions();
-        println!("actions: {:?}", actions);
+        // only hearts may follow, unless the player holds none
+        let hand = &whist.current_player().hand;
+        let hearts: Vec<_> = hand
+            .iter_slots()
+            .filter(|(_, card)| card.suit == Suit::Hearts)
+            .map(|(slot, _)| slot)
+            .collect();
+
+        match hearts.is_empty() {
+            true => assert_eq!(whist.legal_actions(), actions),
+            false => assert_eq!(whist.legal_actions(), hearts),
+        }
     }
 
     #[test]
     fn test_step() {
         let mut whist = Whist::new();
-        println!("TRUMPS ARE: {:?}", whist.trumps);
-        whist.deal();
 
-        for _ in 0..13 {
-            for _ in 0..4 {
-                whist.render();
-                let actions = whist.legal_actions();
-                whist.step(actions[0]);
-            }
+        for _ in 0..52 {
+            let actions = whist.legal_actions();
+            whist.try_step(actions[0]).unwrap();
+        }
 
-            println!("");
+        let score: u8 = whist.table.players.iter().map(|p| p.score).sum();
+        assert_eq!(score, 13);
+
+        for player in whist.table.players.iter() {
+            assert!(player.hand.is_empty());
         }
+    }
 
-        assert!(false);
+    #[test]
+    fn test_bids() {
+        let mut whist = Whist::with_seed(3);
+        whist.table.trumps = None;
+        whist.place_bid(0, 3);
+        whist.place_bid(1, 5);
+        whist.place_bid(2, 2);
+        whist.place_bid(3, 3);
+
+        // last trick of the hand, player 0 leads the winning card
+        let cards = [Rank::Ace, Rank::Two, Rank::Three, Rank::Four];
+        let tricks = [2, 4, 3, 3];
+
+        for (player, (rank, score)) in whist.table.players.iter_mut().zip(cards.iter().zip(tricks))
+        {
+            player.hand = Hand::new();
+            player.hand.add(Card::new(Suit::Hearts, *rank));
+            player.score = score;
+        }
+
+        let mut reward = 0;
+
+        for _ in 0..4 {
+            (_, reward, _) = whist.try_step(0).unwrap();
+        }
+
+        assert_eq!(whist.bids_met(), [true, false, false, true]);
+
+        // player 3 completed the last trick, lost it to player 0's ace
+        // and made their bid
+        assert_eq!(reward, 1);
+
+        // every bid is settled, player 0 also takes the trick
+        assert_eq!(whist.rewards(), [2, -1, -1, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "no player 3 to bid")]
+    fn test_bid_cutthroat() {
+        let mut whist = Whist::builder().players(3).seed(3).build();
+        whist.place_bid(3, 4);
+    }
+
+    #[test]
+    fn test_trick_rewards() {
+        let mut whist = Whist::with_seed(5);
+
+        let mut totals = [0; 4];
+
+        for _ in 0..52 {
+            let id = whist.current_player_index();
+            let actions = whist.legal_actions();
+            let (_, reward, _) = whist.try_step(actions[0]).unwrap();
+
+            // the acting player is rewarded only for a trick they take
+            assert_eq!(reward, whist.rewards()[id]);
+
+            for (total, reward) in totals.iter_mut().zip(whist.rewards()) {
+                *total += reward;
+            }
+        }
+
+        let won: Vec<_> = whist.tricks_won().iter().map(|won| *won as i8).collect();
+        assert_eq!(totals, won.as_slice());
+        assert_eq!(totals.iter().sum::<i8>(), 13);
+    }
+
+    #[test]
+    fn test_step_rewards() {
+        let mut whist = Whist::with_seed(11);
+
+        let mut stepped = [0; 4];
+        let mut shared = [0; 4];
+
+        while !whist.is_terminal() {
+            let id = whist.current_player_index();
+            let action = whist.legal_actions()[0];
+            let (_, reward, _) = Game::step(&mut whist, action);
+
+            stepped[id] += reward;
+
+            for (total, reward) in shared.iter_mut().zip(whist.rewards()) {
+                *total += reward;
+            }
+        }
+
+        // every trick is paid out by rewards, but step only pays a winner
+        // who played the last card of their trick
+        let won: Vec<_> = whist.tricks_won().iter().map(|won| *won as i8).collect();
+        assert_eq!(shared, won.as_slice());
+
+        for (stepped, shared) in stepped.iter().zip(shared) {
+            assert!((0..=shared).contains(stepped));
+        }
+
+        assert!(stepped.iter().sum::<i8>() < shared.iter().sum::<i8>());
+    }
+
+    #[test]
+    fn test_step_after_hand() {
+        let mut whist = Whist::with_seed(7);
+        assert!(!whist.is_terminal());
+
+        for i in 0..52 {
+            let actions = whist.legal_actions();
+            let (_, _, done) = whist.try_step(actions[0]).unwrap();
+            assert_eq!(done, i == 51);
+        }
+
+        assert!(whist.legal_actions().is_empty());
+        assert!(whist.is_terminal());
+
+        let (_, reward, done) = whist.try_step(0).unwrap();
+        assert_eq!(reward, 0);
+        assert!(done);
+
+        // a reset deals the next hand
+        whist.reset();
+        assert!(!whist.is_terminal());
+        assert_eq!(whist.legal_actions().len(), 13);
+        assert!(!Whist::new().is_terminal());
+    }
+
+    #[test]
+    fn test_num_players() {
+        assert_eq!(Whist::new().num_players(), 4);
+    }
+
+    #[test]
+    fn test_winner() {
+        let mut whist = Whist::with_seed(11);
+
+        assert_eq!(whist.winner(), None);
+
+        while !whist.is_terminal() {
+            let actions = whist.legal_actions();
+            whist.try_step(actions[0]).unwrap();
+        }
+
+        let scores = whist.scores();
+        let best = *scores.iter().max().unwrap();
+        assert_eq!(scores.iter().map(|s| *s as u32).sum::<u32>(), 13);
+
+        match whist.winner() {
+            Some(id) => assert_eq!(scores[id as usize], best),
+            None => assert!(scores.iter().filter(|s| **s == best).count() > 1),
+        }
+    }
+
+    #[test]
+    fn test_partnerships() {
+        let mut whist = Whist::with_partnerships();
+
+        let mut rewards = [0; 4];
+
+        while !whist.is_terminal() {
+            let actions = whist.legal_actions();
+            whist.try_step(actions[0]).unwrap();
+
+            for (total, reward) in rewards.iter_mut().zip(whist.rewards()) {
+                *total += reward as u8;
+            }
+        }
+
+        let scores = whist.scores();
+        let teams = whist.team_scores().unwrap();
+
+        assert_eq!(teams[0], scores[0] + scores[2]);
+        assert_eq!(teams[1], scores[1] + scores[3]);
+        assert_eq!(teams[0] + teams[1], 13);
+
+        // every trick reward went to both players of the team that took it
+        assert_eq!(rewards, [teams[0], teams[1], teams[0], teams[1]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "partnerships need four players")]
+    fn test_partnerships_cutthroat() {
+        assert_eq!(Whist::with_players(3).team_scores(), None);
+
+        Whist::builder().players(3).partnerships(true).build();
+    }
+
+    #[test]
+    fn test_clone() {
+        let mut whist = Whist::with_seed(13);
+        whist.try_step(whist.legal_actions()[0]).unwrap();
+
+        let mut clone = whist.clone();
+        clone.try_step(clone.legal_actions()[0]).unwrap();
+
+        assert_eq!(whist.table.seen.len(), 1);
+        assert_eq!(clone.table.seen.len(), 2);
+        assert_ne!(whist.current_player().id, clone.current_player().id);
+    }
+
+    #[test]
+    fn test_with_trumps() {
+        let mut whist = Whist::with_trumps(Some(Suit::Spades));
+        assert_eq!(whist.trumps(), Some(Suit::Spades));
+
+        let cards = [
+            Card::new(Suit::Hearts, Rank::King),
+            Card::new(Suit::Spades, Rank::Two),
+            Card::new(Suit::Hearts, Rank::Ace),
+            Card::new(Suit::Hearts, Rank::Three),
+        ];
+
+        for (player, card) in whist.table.players.iter_mut().zip(cards) {
+            player.hand = Hand::new();
+            player.hand.add(card);
+        }
+
+        for _ in 0..4 {
+            whist.try_step(0).unwrap();
+        }
+
+        assert_eq!(whist.scores(), [0, 1, 0, 0]);
+
+        // the trumps stay fixed for the next hand
+        whist.reset();
+        assert_eq!(whist.trumps(), Some(Suit::Spades));
+
+        let mut no_trumps = Whist::with_trumps(None);
+        assert_eq!(no_trumps.trumps(), None);
+
+        for _ in 0..10 {
+            no_trumps.reset();
+            assert_eq!(no_trumps.trumps(), None);
+        }
+    }
+
+    #[test]
+    fn test_spaces() {
+        let whist = Whist::new();
+
+        assert_eq!(whist.action_space(), ActionSpace::Discrete(13));
+
+        match whist.observation_space() {
+            ObservationSpace::MultiDiscrete(space) => assert_eq!(space.len(), 69),
+            space => panic!("unexpected observation space {:?}", space),
+        }
+    }
+
+    #[test]
+    fn test_encode_observation() {
+        let mut whist = Whist::with_trumps(Some(Suit::Clubs));
+
+        for i in 0..52 {
+            let encoded = whist.encode_observation();
+            assert_eq!(encoded.len(), 160);
+
+            assert_eq!(encoded[52..104].iter().sum::<f32>() as usize, i);
+            assert_eq!(encoded[104 + Suit::Clubs as usize], 1.0);
+
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        }
+
+        assert_eq!(whist.encode_observation().len(), 160);
+    }
+
+    #[test]
+    fn test_unseen() {
+        let mut whist = Whist::with_seed(19);
+
+        let hand = whist.current_player().hand.len();
+        assert_eq!(whist.unseen().len() + hand, 52);
+
+        for _ in 0..5 {
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        }
+
+        let hand = whist.current_player().hand.len();
+        assert_eq!(whist.unseen().len() + hand + 5, 52);
+
+        for card in whist.table.players[1].hand.iter() {
+            assert!(whist.unseen().contains(card));
+        }
+    }
+
+    #[test]
+    fn test_observation_ref() {
+        let mut whist = Whist::with_seed(23);
+
+        for _ in 0..6 {
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        }
+
+        let observation = whist.observation_ref();
+        let (hand, seen, trumps, trick) = whist.observation();
+
+        assert_eq!(*observation.hand, hand);
+        assert_eq!(observation.seen, seen.as_slice());
+        assert_eq!(observation.trumps, trumps);
+        assert_eq!(observation.trick, trick.as_slice());
+
+        // the view borrows the game's own storage rather than copying it
+        assert!(std::ptr::eq(observation.hand, &whist.current_player().hand));
+        assert!(std::ptr::eq(observation.seen, whist.table.seen.as_slice()));
+        assert!(std::ptr::eq(
+            observation.trick,
+            whist.table.trick.as_slice()
+        ));
+    }
+
+    #[test]
+    fn test_illegal_actions() {
+        let mut whist = Whist::with_trumps(None);
+
+        assert_eq!(whist.try_step(13), Err(IllegalAction(13)));
+
+        for _ in 0..4 {
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        }
+
+        // the slot the trick winner played from is now empty
+        let id = whist.current_player().id;
+        let empty = whist
+            .current_player()
+            .hand
+            .slots()
+            .iter()
+            .position(|c| c.is_none());
+        let empty = empty.unwrap() as u8;
+
+        assert_eq!(whist.try_step(empty), Err(IllegalAction(empty as u32)));
+        assert_eq!(whist.current_player().id, id);
+
+        // a legal action steps the same either way
+        let action = whist.legal_actions()[0];
+        let mut checked = whist.clone();
+        assert_eq!(
+            Game::step(&mut whist, action),
+            checked.try_step(action).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_revoke() {
+        let mut whist = Whist::with_trumps(None);
+
+        let hands = [
+            [
+                Card::new(Suit::Hearts, Rank::Ace),
+                Card::new(Suit::Clubs, Rank::Two),
+            ],
+            [
+                Card::new(Suit::Spades, Rank::Ace),
+                Card::new(Suit::Hearts, Rank::Two),
+            ],
+        ];
+
+        for (player, hand) in whist.table.players.iter_mut().zip(hands) {
+            player.hand = Hand::new();
+            player.hand.add(hand[0]);
+            player.hand.add(hand[1]);
+        }
+
+        whist.try_step(0).unwrap();
+
+        // player 1 holds a heart so cannot discard the spade
+        assert_eq!(whist.try_step(0), Err(IllegalAction(0)));
+        assert_eq!(whist.table.trick.len(), 1);
+        assert!(whist.try_step(1).is_ok());
+    }
+
+    #[test]
+    fn test_misere() {
+        let mut normal = Whist::with_trumps(None);
+        let mut misere = Whist::builder()
+            .trumps(None)
+            .scoring(ScoringMode::Misere)
+            .build();
+
+        let hands: [&[&str]; 4] = [
+            &["2H", "2S", "3C", "4D"],
+            &["3H", "3S", "AC", "2D"],
+            &["4H", "4S", "4C", "AD"],
+            &["AH", "AS", "2C", "3D"],
+        ];
+
+        for whist in [&mut normal, &mut misere] {
+            for (player, cards) in whist.table.players.iter_mut().zip(hands) {
+                player.hand = Hand::new();
+
+                for card in cards {
+                    player.hand.add(card.parse().unwrap());
+                }
+            }
+        }
+
+        // player 3 takes the first two tricks, then players 1 and 2 one each
+        let plays = [
+            "2H", "3H", "4H", "AH", "AS", "2S", "3S", "4S", "2C", "3C", "AC", "4C", "2D", "AD",
+            "3D", "4D",
+        ];
+
+        for card in plays {
+            let card: Card = card.parse().unwrap();
+            normal.play_card(card).unwrap();
+            misere.play_card(card).unwrap();
+
+            // taking a trick is a penalty instead
+            let negated: Vec<_> = normal.rewards().iter().map(|reward| -reward).collect();
+            assert_eq!(misere.rewards(), negated);
+        }
+
+        assert!(normal.is_terminal() && misere.is_terminal());
+        assert_eq!(normal.scores(), [0, 1, 1, 2]);
+        assert_eq!(misere.scores(), [4, 3, 3, 2]);
+
+        // the most tricks win normally but the fewest win in misere
+        assert_eq!(normal.winner(), Some(3));
+        assert_eq!(misere.winner(), Some(0));
+    }
+
+    #[test]
+    fn test_render_to() {
+        let mut whist = Whist::with_trumps(Some(Suit::Hearts));
+        whist.try_step(0).unwrap();
+
+        let mut output = Vec::new();
+        whist.render_to(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains(&format!("{:?}", whist.table.trick[0])));
+        assert!(output.contains("Trump: Some(Hearts)"));
+    }
+
+    #[test]
+    fn test_step_many() {
+        let mut whist = Whist::with_seed(31);
+
+        // find a legal trick on a copy and replay it in one go
+        let mut copy = whist.clone();
+        let mut actions = Vec::new();
+
+        for _ in 0..4 {
+            let action = copy.legal_actions()[0];
+            copy.try_step(action).unwrap();
+            actions.push(action);
+        }
+
+        let ((hand, seen, _, trick), rewards, done) = whist.step_many(actions);
+
+        // the last card completes the trick, rewarding whoever played it if they took it
+        let completed = &copy.history()[0];
+        let taken = (completed.plays[3].0 == completed.winner) as i8;
+        assert_eq!(rewards, vec![0, 0, 0, taken]);
+        assert!(!done);
+        assert_eq!(seen.len(), 4);
+        assert!(trick.is_empty());
+        assert_eq!(hand, copy.current_player().hand);
+        assert_eq!(whist.scores(), copy.scores());
+    }
+
+    #[test]
+    fn test_current_trick_leader() {
+        let mut whist = Whist::with_trumps(Some(Suit::Spades));
+
+        let cards = [
+            Card::new(Suit::Hearts, Rank::Five),
+            Card::new(Suit::Hearts, Rank::King),
+            Card::new(Suit::Spades, Rank::Two),
+            Card::new(Suit::Hearts, Rank::Ace),
+        ];
+
+        for (player, card) in whist.table.players.iter_mut().zip(cards) {
+            player.hand = Hand::new();
+            player.hand.add(card);
+        }
+
+        assert_eq!(whist.current_trick_leader(), None);
+
+        let mut leaders = Vec::new();
+
+        for _ in 0..3 {
+            whist.try_step(0).unwrap();
+            leaders.push(whist.current_trick_leader().unwrap());
+        }
+
+        // the trump played third overtakes the king
+        assert_eq!(leaders, vec![0, 1, 2]);
+
+        whist.try_step(0).unwrap();
+        assert_eq!(whist.scores(), [0, 0, 1, 0]);
+        assert_eq!(whist.current_trick_leader(), None);
+    }
+
+    #[test]
+    fn test_play_card() {
+        let mut whist = Whist::with_trumps(None);
+
+        let hands = [
+            [
+                Card::new(Suit::Hearts, Rank::Ace),
+                Card::new(Suit::Clubs, Rank::Two),
+            ],
+            [
+                Card::new(Suit::Spades, Rank::Ace),
+                Card::new(Suit::Hearts, Rank::Two),
+            ],
+        ];
+
+        for (player, hand) in whist.table.players.iter_mut().zip(hands) {
+            player.hand = Hand::new();
+            player.hand.add(hand[0]);
+            player.hand.add(hand[1]);
+        }
+
+        let (_, _, done) = whist.play_card(hands[0][0]).unwrap();
+        assert!(!done);
+        assert_eq!(whist.table.trick, vec![hands[0][0]]);
+
+        // already played, and held by someone else
+        assert_eq!(
+            whist.play_card(hands[0][0]),
+            Err(PlayError::NotHeld(hands[0][0]))
+        );
+        assert_eq!(
+            whist.play_card(hands[0][1]),
+            Err(PlayError::NotHeld(hands[0][1]))
+        );
+
+        // a revoke when holding a heart
+        assert_eq!(
+            whist.play_card(hands[1][0]),
+            Err(PlayError::Illegal(IllegalAction(0)))
+        );
+
+        assert!(whist.play_card(hands[1][1]).is_ok());
+        assert_eq!(whist.table.trick.len(), 2);
+
+        // both fit the crate's error type
+        assert_eq!(
+            CardsError::from(PlayError::NotHeld(hands[0][0])),
+            CardsError::CardNotHeld(hands[0][0])
+        );
+        assert_eq!(
+            CardsError::from(PlayError::Illegal(IllegalAction(0))),
+            CardsError::IllegalAction(0)
+        );
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut whist = Whist::with_seed(41);
+        assert!(!whist.undo());
+
+        let mut observations = vec![whist.observation()];
+        let mut players = vec![whist.current_player().id];
+
+        // across the end of the first trick
+        for _ in 0..6 {
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+            observations.push(whist.observation());
+            players.push(whist.current_player().id);
+        }
+
+        assert_eq!(whist.scores().iter().sum::<u8>(), 1);
+
+        for _ in 0..6 {
+            observations.pop();
+            players.pop();
+
+            assert!(whist.undo());
+            assert_eq!(whist.observation(), *observations.last().unwrap());
+            assert_eq!(whist.current_player().id, *players.last().unwrap());
+        }
+
+        assert_eq!(whist.scores(), [0; 4]);
+        assert!(!whist.undo());
+    }
+
+    #[test]
+    fn test_three_players() {
+        let mut whist = Whist::with_players(3);
+
+        assert_eq!(whist.num_players(), 3);
+        assert_eq!(whist.action_space(), ActionSpace::Discrete(17));
+        assert_eq!(whist.kitty().len(), 1);
+
+        let mut cards = whist.kitty().to_vec();
+
+        for player in whist.table.players.iter() {
+            assert_eq!(player.hand.len(), 17);
+            cards.extend(player.hand.iter());
+        }
+
+        // every card is dealt once, to a hand or the kitty
+        cards.sort();
+        let deck: Vec<_> = (0..52).map(|i| Card::try_from(i).unwrap()).collect();
+        assert_eq!(cards, deck);
+
+        let transitions = whist.clone().play(|whist| whist.legal_actions()[0]).count();
+        assert_eq!(transitions, 51);
+
+        while !whist.is_terminal() {
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        }
+
+        assert_eq!(whist.scores().iter().sum::<u8>(), 17);
+    }
+
+    #[test]
+    fn test_builder() {
+        let mut whist = Whist::builder()
+            .seed(43)
+            .trumps(Some(Suit::Diamonds))
+            .scoring(ScoringMode::Misere)
+            .bid_scoring(BidScoring::BidWhist {
+                overtrick: 1,
+                undertrick: 2,
+            })
+            .partnerships(true)
+            .players(4)
+            .build();
+
+        assert_eq!(whist.trumps(), Some(Suit::Diamonds));
+        assert_eq!(whist.scoring(), ScoringMode::Misere);
+        assert!(whist.partnerships());
+        assert_eq!(whist.num_players(), 4);
+
+        // the seed deals the same cards as with_seed
+        let seeded = Whist::with_seed(43);
+
+        for (a, b) in whist.table.players.iter().zip(seeded.table.players.iter()) {
+            assert_eq!(a.hand, b.hand);
+        }
+
+        let mut cutthroat = Whist::builder()
+            .players(3)
+            .trumps(None)
+            .hand_size(7)
+            .build();
+        assert_eq!(cutthroat.num_players(), 3);
+        assert_eq!(cutthroat.trumps(), None);
+        assert!(!cutthroat.partnerships());
+
+        // every setting carries over to the next hand
+        whist.reset();
+        assert_eq!(whist.trumps(), Some(Suit::Diamonds));
+        assert_eq!(whist.scoring(), ScoringMode::Misere);
+        assert!(whist.partnerships());
+        assert_eq!(whist.num_players(), 4);
+        assert_eq!(
+            whist.bid_scoring(),
+            BidScoring::BidWhist {
+                overtrick: 1,
+                undertrick: 2
+            }
+        );
+
+        cutthroat.reset();
+        assert_eq!(cutthroat.num_players(), 3);
+        assert_eq!(cutthroat.trumps(), None);
+        assert_eq!(cutthroat.action_space(), ActionSpace::Discrete(7));
+        assert_eq!(cutthroat.kitty.len(), 52 - 3 * 7);
+        assert_eq!(cutthroat.bid_scoring(), BidScoring::Exact);
+    }
+
+    #[test]
+    fn test_tricks_won() {
+        let mut whist = Whist::with_scoring(ScoringMode::Misere);
+
+        for i in 0..9 {
+            assert_eq!(whist.tricks_won().iter().sum::<u8>(), i / 4);
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        }
+
+        // the third trick is in progress and not counted
+        let tricks = whist.tricks_won();
+        assert_eq!(tricks.len(), 4);
+        assert_eq!(tricks.iter().sum::<u8>(), 2);
+        assert_eq!(whist.table.trick.len(), 1);
+    }
+
+    #[test]
+    fn test_reseed() {
+        let mut a = Whist::new();
+        let mut b = Whist::new();
+
+        for whist in [&mut a, &mut b] {
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+            whist.reseed(47);
+            whist.reset();
+        }
+
+        assert_eq!(a.trumps(), b.trumps());
+
+        for (pa, pb) in a.table.players.iter().zip(b.table.players.iter()) {
+            assert_eq!(pa.hand, pb.hand);
+        }
+    }
+
+    #[test]
+    fn test_dealer() {
+        let mut whist = Whist::with_seed(53);
+        assert_eq!(whist.dealer(), 3);
+
+        whist.set_dealer(2);
+
+        let mut deck = Deck::new();
+        deck.shuffle(&mut whist.rng.clone());
+        let first = deck.draw().unwrap();
+
+        whist.deal();
+
+        // player 3 sits left of the dealer, so is dealt to first and leads
+        assert_eq!(whist.current_player().id, 3);
+        assert_eq!(whist.current_player().hand[0], Some(first));
+
+        let order: Vec<_> = whist.table.players.iter().map(|p| p.id).collect();
+        assert_eq!(order, vec![3, 0, 1, 2]);
+
+        whist.reset();
+        assert_eq!(whist.dealer(), 2);
+    }
+
+    #[test]
+    fn test_default() {
+        let mut whist = Whist::with_players(3);
+        let cutthroat = std::mem::take(&mut whist);
+
+        assert_eq!(cutthroat.num_players(), 3);
+        assert_eq!(whist.num_players(), 4);
+        assert_eq!(whist.scoring(), ScoringMode::Normal);
+
+        assert_eq!(whist.legal_actions().len(), 13);
+    }
+
+    #[test]
+    fn test_history() {
+        let mut whist = Whist::with_seed(59);
+
+        while !whist.is_terminal() {
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        }
+
+        let history = whist.history();
+        assert_eq!(history.len(), 13);
+
+        let seen: Vec<_> = history
+            .iter()
+            .flat_map(|trick| trick.plays.iter().map(|(_, card)| *card))
+            .collect();
+        assert_eq!(seen, whist.table.seen.as_slice());
+
+        // each trick's winner leads the next and the scores match the winners
+        for (trick, next) in history.iter().zip(history.iter().skip(1)) {
+            assert_eq!(trick.winner, next.leader);
+            assert_eq!(trick.plays.len(), 4);
+        }
+
+        let mut won = vec![0; 4];
+
+        for trick in history {
+            won[trick.winner as usize] += 1;
+        }
+
+        assert_eq!(won, whist.tricks_won());
+    }
+
+    #[test]
+    fn test_revokes() {
+        let mut whist = Whist::with_seed(59);
+
+        // play legally until someone must follow suit but holds other cards
+        let (slot, id) = loop {
+            let legal = whist.legal_actions();
+            let illegal = whist
+                .current_player()
+                .hand
+                .iter_slots()
+                .map(|(slot, _)| slot)
+                .find(|slot| !legal.contains(slot));
+
+            if let Some(slot) = illegal {
+                break (slot, whist.current_player().id);
+            }
+
+            whist.try_step(legal[0]).unwrap();
+        };
+
+        assert!(whist.try_step(slot).is_err());
+
+        let trick = whist.history().len();
+        whist.step_unchecked(slot);
+
+        while whist.history().len() == trick {
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        }
+
+        assert_eq!(whist.revokes(), vec![(trick, id)]);
+
+        // taking the trick back forgets the revoke
+        while whist.history().len() > trick {
+            assert!(whist.undo());
+        }
+
+        assert!(whist.revokes().is_empty());
+    }
+
+    #[test]
+    fn test_all_observations() {
+        let mut whist = Whist::with_seed(61);
+
+        for _ in 0..9 {
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        }
+
+        let observations = whist.all_observations();
+        assert_eq!(observations.len(), 4);
+
+        // the current player's view matches observation_ref
+        let current = whist.current_player().id as usize;
+        assert_eq!(observations[current].hand, whist.observation_ref().hand);
+
+        let mut held: Vec<_> = observations
+            .iter()
+            .flat_map(|observation| observation.hand.iter().copied())
+            .collect();
+        held.sort();
+
+        let before = held.len();
+        held.dedup();
+        assert_eq!(held.len(), before);
+
+        let mut unplayed: Vec<_> = standard::deck()
+            .into_iter()
+            .filter(|card| !whist.table.seen.contains(card))
+            .collect();
+        unplayed.sort();
+
+        assert_eq!(held, unplayed);
+    }
+
+    #[test]
+    fn test_full_state() {
+        let whist = Whist::with_seed(67);
+        let state = whist.full_state();
+
+        assert_eq!(state.hands.len(), 4);
+        assert_eq!(state.current_player, whist.current_player().id);
+
+        let mut cards: Vec<_> = state
+            .hands
+            .iter()
+            .flat_map(|hand| hand.iter().copied())
+            .chain(state.kitty.iter().copied())
+            .chain(state.seen.iter().copied())
+            .collect();
+        cards.sort();
+
+        let mut deck = standard::deck().to_vec();
+        deck.sort();
+
+        assert_eq!(cards, deck);
+    }
+
+    #[test]
+    fn test_hand_size() {
+        let mut whist = Whist::builder().seed(73).hand_size(7).build();
+
+        assert!(whist
+            .table
+            .players
+            .iter()
+            .all(|player| player.hand.len() == 7));
+        assert_eq!(whist.kitty().len(), 24);
+        assert_eq!(whist.action_space(), ActionSpace::Discrete(7));
+
+        for _ in 0..4 {
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        }
+
+        assert_eq!(whist.history().len(), 1);
+        assert!(whist
+            .table
+            .players
+            .iter()
+            .all(|player| player.hand.len() == 6));
+
+        whist.reset();
+        assert_eq!(whist.kitty().len(), 24);
+    }
+
+    #[test]
+    fn test_known_voids() {
+        let mut whist = Whist::with_seed(79);
+
+        assert!(whist.known_voids().iter().flatten().all(|void| !void));
+
+        // play until someone cannot follow the leading suit
+        let (id, leading) = loop {
+            let player = whist.current_player();
+
+            if let Some(leading) = whist.table.trick.first().map(|card| card.suit) {
+                if !player.hand.iter().any(|card| card.suit == leading) {
+                    break (player.id, leading);
+                }
+            }
+
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        };
+
+        assert!(!whist.known_voids()[id as usize][leading as usize]);
+
+        whist.try_step(whist.legal_actions()[0]).unwrap();
+        let voids = whist.known_voids();
+
+        assert!(voids[id as usize][leading as usize]);
+        assert_eq!(voids.iter().flatten().filter(|void| **void).count(), 1);
+    }
+
+    #[test]
+    fn test_bid_scoring() {
+        let scoring = BidScoring::BidWhist {
+            overtrick: 1,
+            undertrick: 2,
+        };
+
+        assert_eq!(scoring.reward(4, 4), 4);
+        assert_eq!(scoring.reward(4, 5), 5);
+        assert_eq!(scoring.reward(4, 2), -4);
+
+        assert_eq!(BidScoring::Exact.reward(4, 4), 1);
+        assert_eq!(BidScoring::Exact.reward(4, 5), -1);
+
+        // player 3 bid 2 and takes the last trick for 3, one overtrick
+        let mut whist = Whist::builder().seed(3).bid_scoring(scoring).build();
+        whist.table.trumps = None;
+        whist.place_bid(3, 2);
+
+        let cards = [Rank::Two, Rank::Three, Rank::Four, Rank::Ace];
+
+        for (player, rank) in whist.table.players.iter_mut().zip(cards) {
+            player.hand = Hand::new();
+            player.hand.add(Card::new(Suit::Hearts, rank));
+            player.score = 2;
+        }
+
+        let mut reward = 0;
+
+        for _ in 0..4 {
+            (_, reward, _) = whist.try_step(0).unwrap();
+        }
+
+        assert_eq!(reward, 1 + 3);
+        assert_eq!(whist.reward_bounds(), (-26.0, 14.0));
+
+        // 13 overtricks at 10 each is more than an i8 holds
+        let steep = BidScoring::BidWhist {
+            overtrick: 10,
+            undertrick: 1,
+        };
+        assert_eq!(steep.reward(0, 13), i8::MAX);
+
+        // but fits a short enough hand
+        let whist = Whist::builder().bid_scoring(steep).hand_size(7).build();
+        assert_eq!(whist.reward_bounds(), (-7.0, 71.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "do not fit in an i8")]
+    fn test_bid_scoring_overflow() {
+        let steep = BidScoring::BidWhist {
+            overtrick: 10,
+            undertrick: 1,
+        };
+
+        Whist::builder().bid_scoring(steep).build();
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let mut whist = Whist::with_seed(83);
+
+        for _ in 0..6 {
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        }
+
+        let snapshot = whist.snapshot();
+        let state = whist.full_state();
+
+        while !whist.is_terminal() {
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        }
+
+        whist.restore(snapshot);
+
+        assert_eq!(whist.full_state(), state);
+        assert_eq!(whist.history().len(), 1);
+    }
+
+    #[test]
+    fn test_current_player_index() {
+        let mut whist = Whist::with_seed(89);
+        whist.set_dealer(1);
+        whist.deal();
+
+        // the player after the dealer leads
+        assert_eq!(whist.current_player_index(), 2);
+
+        whist.try_step(whist.legal_actions()[0]).unwrap();
+        assert_eq!(whist.current_player_index(), 3);
+
+        for _ in 0..3 {
+            whist.try_step(whist.legal_actions()[0]).unwrap();
+        }
+
+        let winner = whist.history()[0].winner as usize;
+        assert_eq!(whist.current_player_index(), winner);
+    }
+
+    #[test]
+    fn test_possible_outcomes() {
+        let mut whist = Whist::with_seed(3);
+        whist.table.trumps = Some(Suit::Spades);
+
+        let hands: [&[&str]; 4] = [&["9H"], &["KH"], &["2H", "3S", "AH"], &["4H"]];
+
+        for (player, cards) in whist.table.players.iter_mut().zip(hands) {
+            player.hand = Hand::new();
+
+            for card in cards {
+                player.hand.add(card.parse().unwrap());
+            }
+        }
+
+        // players 0 and 1 lead the nine and king of hearts
+        whist.try_step(0).unwrap();
+        whist.try_step(0).unwrap();
+
+        // player 2 must follow, the two leaves player 1 winning and the ace wins
+        assert_eq!(whist.possible_outcomes(), vec![(0, 1), (2, 2)]);
+
+        for (action, leader) in whist.possible_outcomes() {
+            let mut copy = whist.clone();
+            copy.try_step(action).unwrap();
+            assert_eq!(copy.current_trick_leader(), Some(leader));
+        }
     }
 }
@@ -1,2 +1,7 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
 pub mod card;
+#[cfg(feature = "std")]
+pub mod error;
+#[cfg(feature = "std")]
 pub mod game;